@@ -5,7 +5,7 @@ use ratatui::crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers, Mous
 use ratatui::layout::{Position, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::Span;
-use ratatui::widgets::{Block, Scrollbar, ScrollbarOrientation};
+use ratatui::widgets::Block;
 use ratatui::{crossterm, Frame, Terminal};
 use tui_tree_widget::{Tree, TreeItem, TreeState};
 
@@ -89,12 +89,9 @@ impl App {
                     .title("Tree Widget")
                     .title_bottom(format!("{:?}", self.state)),
             )
-            .experimental_scrollbar(Some(
-                Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                    .begin_symbol(None)
-                    .track_symbol(None)
-                    .end_symbol(None),
-            ))
+            .scrollbar_begin_symbol(None)
+            .scrollbar_track_symbol(None)
+            .scrollbar_end_symbol(None)
             .highlight_style(
                 Style::new()
                     .fg(Color::Black)
@@ -164,8 +161,8 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> std::io::Res
                     KeyCode::Esc => app.state.select(Vec::new()),
                     KeyCode::Home => app.state.select_first(),
                     KeyCode::End => app.state.select_last(),
-                    KeyCode::PageDown => app.state.scroll_down(3),
-                    KeyCode::PageUp => app.state.scroll_up(3),
+                    KeyCode::PageDown => app.state.page_down_selection(),
+                    KeyCode::PageUp => app.state.page_up_selection(),
                     _ => false,
                 },
                 Event::Mouse(mouse) => match mouse.kind {
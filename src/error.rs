@@ -0,0 +1,26 @@
+use std::fmt;
+
+/// Error when creating a [`Tree`](crate::Tree) or [`TreeItem`](crate::TreeItem) with identifiers which are not unique.
+///
+/// The identifier of each item needs to be unique among its siblings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TreeItemError {
+    /// The given items contain duplicate identifiers.
+    DuplicateIdentifiers,
+    /// The identifier of the item to add already exists in the children.
+    IdentifierAlreadyExists,
+}
+
+impl fmt::Display for TreeItemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateIdentifiers => f.write_str("The items contain duplicate identifiers"),
+            Self::IdentifierAlreadyExists => {
+                f.write_str("identifier already exists in the children")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TreeItemError {}
@@ -1,57 +1,180 @@
+/*!
+Flatten the nested [`TreeItem`]s into a list of what is shown.
+
+Helpful when building something on top of [`TreeItem`]s without rendering a [`Tree`](crate::Tree).
+Usually [`TreeState::flatten`](crate::TreeState::flatten) and [`TreeItem::flatten_all`] are simpler to use.
+*/
+
 use std::collections::HashSet;
+use std::hash::BuildHasher;
 
 use crate::tree_item::TreeItem;
 
 /// A flattened item of all visible [`TreeItem`]s.
 ///
 /// Generated via [`TreeState::flatten`](crate::TreeState::flatten).
+///
+/// Marked as `non_exhaustive` so further fields can be added without breaking changes.
+/// Use [`Flattened::new`] to create one manually.
 #[must_use]
+#[non_exhaustive]
 pub struct Flattened<'text, Identifier> {
     pub identifier: Vec<Identifier>,
     pub item: &'text TreeItem<'text, Identifier>,
 }
 
-impl<Identifier> Flattened<'_, Identifier> {
+impl<'text, Identifier> Flattened<'text, Identifier> {
+    /// Create a new `Flattened` item.
+    ///
+    /// The `identifier` is the full path to the `item` including the identifier of the `item` itself.
+    pub const fn new(
+        identifier: Vec<Identifier>,
+        item: &'text TreeItem<'text, Identifier>,
+    ) -> Self {
+        Self { identifier, item }
+    }
+
     /// Zero based depth. Depth 0 means top level with 0 indentation.
     #[must_use]
-    pub fn depth(&self) -> usize {
+    pub const fn depth(&self) -> usize {
         self.identifier.len() - 1
     }
+
+    /// Top level item without any parent. Same as `depth() == 0`.
+    #[must_use]
+    pub const fn is_root(&self) -> bool {
+        self.identifier.len() == 1
+    }
+
+    /// Item without any children.
+    #[must_use]
+    pub const fn is_leaf(&self) -> bool {
+        self.item.children.is_empty()
+    }
+}
+
+/// Debug friendly one line representation like `[depth=1, id=b/d, has_children=true, height=1]`.
+impl<Identifier> core::fmt::Display for Flattened<'_, Identifier>
+where
+    Identifier: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[depth={}, id=", self.depth())?;
+        for (index, part) in self.identifier.iter().enumerate() {
+            if index > 0 {
+                f.write_str("/")?;
+            }
+            write!(f, "{part}")?;
+        }
+        write!(
+            f,
+            ", has_children={}, height={}]",
+            !self.is_leaf(),
+            self.item.text.height()
+        )
+    }
 }
 
 /// Get a flat list of all visible [`TreeItem`]s.
 ///
-/// `current` starts empty: `&[]`
+/// Children are only included when their parent is in `open_identifiers`.
+/// `current` is the identifier of the parent of the `items` and starts empty: `&[]`
+///
+/// # Example
+///
+/// ```
+/// # use std::collections::HashSet;
+/// # use tui_tree_widget::flatten::flatten;
+/// # use tui_tree_widget::TreeItem;
+/// let items = vec![
+///     TreeItem::new("a", "Alfa", vec![TreeItem::new_leaf("b", "Bravo")])?,
+///     TreeItem::new("c", "Charlie", vec![TreeItem::new_leaf("d", "Delta")])?,
+/// ];
+/// let open = HashSet::from([vec!["a"]]);
+/// let identifiers = flatten(&open, &items, &[])
+///     .into_iter()
+///     .map(|flattened| flattened.identifier)
+///     .collect::<Vec<_>>();
+/// assert_eq!(identifiers, [vec!["a"], vec!["a", "b"], vec!["c"]]);
+/// # Ok::<(), tui_tree_widget::TreeItemError>(())
+/// ```
 #[must_use]
-pub fn flatten<'text, Identifier>(
-    open_identifiers: &HashSet<Vec<Identifier>>,
+pub fn flatten<'text, Identifier, S>(
+    open_identifiers: &HashSet<Vec<Identifier>, S>,
     items: &'text [TreeItem<'text, Identifier>],
     current: &[Identifier],
 ) -> Vec<Flattened<'text, Identifier>>
 where
     Identifier: Clone + PartialEq + Eq + core::hash::Hash,
+    S: BuildHasher,
 {
-    let mut result = Vec::new();
-    for item in items {
-        let mut child_identifier = current.to_vec();
-        child_identifier.push(item.identifier.clone());
+    flatten_where(items, current, |identifier| {
+        open_identifiers.contains(identifier)
+    })
+}
 
-        let child_result = open_identifiers
-            .contains(&child_identifier)
-            .then(|| flatten(open_identifiers, &item.children, &child_identifier));
+/// Get a flat list of all [`TreeItem`]s as if every node would be open.
+///
+/// Same as [`TreeItem::flatten_all`].
+#[must_use]
+pub fn flatten_all<'text, Identifier>(
+    items: &'text [TreeItem<'text, Identifier>],
+) -> Vec<Flattened<'text, Identifier>>
+where
+    Identifier: Clone + PartialEq + Eq + core::hash::Hash,
+{
+    flatten_where(items, &[], |_| true)
+}
 
-        result.push(Flattened {
-            identifier: child_identifier,
-            item,
-        });
+fn flatten_where<'text, Identifier, IsOpen>(
+    items: &'text [TreeItem<'text, Identifier>],
+    current: &[Identifier],
+    is_open: IsOpen,
+) -> Vec<Flattened<'text, Identifier>>
+where
+    Identifier: Clone + PartialEq + Eq + core::hash::Hash,
+    IsOpen: Fn(&[Identifier]) -> bool,
+{
+    let mut result = Vec::new();
+    // Explicit stack instead of recursion to not overflow on deeply nested items
+    let mut stack = vec![(current.to_vec(), items.iter())];
+    while let Some((parent, children)) = stack.last_mut() {
+        let Some(item) = children.next() else {
+            stack.pop();
+            continue;
+        };
+
+        let mut child_identifier = parent.clone();
+        child_identifier.push(item.identifier.clone());
 
-        if let Some(mut child_result) = child_result {
-            result.append(&mut child_result);
+        if is_open(&child_identifier) {
+            stack.push((child_identifier.clone(), item.children.iter()));
         }
+
+        result.push(Flattened::new(child_identifier, item));
     }
     result
 }
 
+#[test]
+fn new_works() {
+    let item = TreeItem::new_leaf("a", "Alfa");
+    let flattened = Flattened::new(vec!["b", "a"], &item);
+    assert_eq!(flattened.depth(), 1);
+    assert!(!flattened.is_root());
+    assert!(flattened.is_leaf());
+}
+
+#[test]
+fn display_works() {
+    let items = TreeItem::example();
+    let flattened = Flattened::new(vec!["b", "d"], &items[1].children()[1]);
+    assert_eq!(
+        flattened.to_string(),
+        "[depth=1, id=b/d, has_children=true, height=1]"
+    );
+}
+
 #[test]
 fn depth_works() {
     let mut open = HashSet::new();
@@ -64,6 +187,18 @@ fn depth_works() {
     assert_eq!(depths, [0, 0, 1, 1, 2, 2, 1, 0]);
 }
 
+#[test]
+fn is_root_and_is_leaf_work() {
+    let mut open = HashSet::new();
+    open.insert(vec!["b"]);
+    let items = TreeItem::example();
+    let flattened = flatten(&open, &items, &[]);
+    let roots = flattened.iter().map(Flattened::is_root).collect::<Vec<_>>();
+    assert_eq!(roots, [true, true, false, false, false, true]);
+    let leafs = flattened.iter().map(Flattened::is_leaf).collect::<Vec<_>>();
+    assert_eq!(leafs, [true, false, true, false, true, true]);
+}
+
 #[cfg(test)]
 fn flatten_works(open: &HashSet<Vec<&'static str>>, expected: &[&str]) {
     let items = TreeItem::example();
@@ -103,3 +238,42 @@ fn flatten_all_open() {
     open.insert(vec!["b", "d"]);
     flatten_works(&open, &["a", "b", "c", "d", "e", "f", "g", "h"]);
 }
+
+#[test]
+fn flatten_deeply_nested_does_not_overflow() {
+    const DEPTH: usize = 5_000;
+    // Zero sized identifiers keep the memory of all the nested identifier paths small
+    let mut item = TreeItem::new_leaf((), "leaf");
+    for _ in 1..DEPTH {
+        item = TreeItem::new((), "node", vec![item]).unwrap();
+    }
+    let open = (1..DEPTH)
+        .map(|depth| vec![(); depth])
+        .collect::<HashSet<_>>();
+    let items = [item];
+    let result = flatten(&open, &items, &[]);
+    assert_eq!(result.len(), DEPTH);
+    assert_eq!(result.last().unwrap().depth(), DEPTH - 1);
+}
+
+#[test]
+fn flatten_all_works() {
+    let items = TreeItem::example();
+    let identifiers = flatten_all(&items)
+        .into_iter()
+        .map(|flattened| flattened.identifier)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        identifiers,
+        [
+            vec!["a"],
+            vec!["b"],
+            vec!["b", "c"],
+            vec!["b", "d"],
+            vec!["b", "d", "e"],
+            vec!["b", "d", "f"],
+            vec!["b", "g"],
+            vec!["h"],
+        ]
+    );
+}
@@ -6,18 +6,25 @@ The user interaction state (like the current selection) is stored in the [`TreeS
 */
 
 use std::collections::HashSet;
+use std::rc::Rc;
 
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Style;
-use ratatui::widgets::{Block, Scrollbar, ScrollbarState, StatefulWidget, Widget};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{
+    Block, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget, Widget,
+};
 use unicode_width::UnicodeWidthStr;
 
+pub use crate::error::TreeItemError;
 pub use crate::flatten::Flattened;
-pub use crate::tree_item::TreeItem;
-pub use crate::tree_state::TreeState;
+pub use crate::node::Node;
+pub use crate::tree_item::{TreeItem, TreeItemIter};
+pub use crate::tree_state::{TreeState, TreeStateDebugView, TreeStateDiff};
 
-mod flatten;
+mod error;
+pub mod flatten;
+mod node;
 mod tree_item;
 mod tree_state;
 
@@ -60,10 +67,53 @@ pub struct Tree<'a, Identifier> {
     /// Style used as a base style for the widget
     style: Style,
 
+    /// Message shown when there is nothing to render
+    empty_message: Option<&'a str>,
+    /// Style used to render the `empty_message`
+    empty_style: Style,
+
+    /// Style used for all rows except the selected one
+    item_style: Style,
+    /// Styles used for even and odd rendered rows
+    zebra_striping: Option<(Style, Style)>,
+
+    /// Rows scrolled per render towards the target offset. 0 disables the animation.
+    #[cfg(feature = "animation")]
+    scroll_animation_speed: u8,
+
+    /// Show the index of each row in front of it
+    line_numbers: bool,
+    /// Number shown for the first row
+    line_numbers_base: usize,
+    /// Style used to render the line numbers
+    line_number_style: Style,
+
     /// Style used to render selected item
     highlight_style: Style,
+    /// Whether the widget is focused. When not, the `unfocused_highlight_style` is used.
+    focused: bool,
+    /// Style used to render selected item while the widget is not focused
+    unfocused_highlight_style: Style,
     /// Symbol in front of the selected item (Shift all items to the right)
     highlight_symbol: &'a str,
+    /// Columns reserved for the `highlight_symbol` instead of its width
+    highlight_symbol_reserved_width: Option<u16>,
+    /// Style used to render marked items
+    marked_style: Style,
+
+    /// Style used to render separator items
+    separator_style: Style,
+    /// Character filling a row between top level nodes
+    top_level_separator: Option<char>,
+    /// Empty rows between top level nodes
+    gap_between_top_level_items: u16,
+
+    /// Amount of spaces per depth level in front of a node
+    indent_width: u16,
+    /// Symbol displayed in the indentation of ancestors which are continued below
+    node_indent_guide: Option<&'a str>,
+    /// Style used to render the `node_indent_guide`
+    node_indent_guide_style: Style,
 
     /// Symbol displayed in front of a closed node (As in the children are currently not visible)
     node_closed_symbol: &'a str,
@@ -71,6 +121,18 @@ pub struct Tree<'a, Identifier> {
     node_open_symbol: &'a str,
     /// Symbol displayed in front of a node without children.
     node_no_children_symbol: &'a str,
+    /// Per depth replacement of the `node_closed_symbol`. The last one is used for deeper nodes.
+    node_closed_symbols: Vec<&'a str>,
+    /// Per depth replacement of the `node_open_symbol`. The last one is used for deeper nodes.
+    node_open_symbols: Vec<&'a str>,
+    /// Nodes deeper than this are not rendered
+    max_depth: Option<usize>,
+    /// Symbol displayed in front of a node whose children are deeper than `max_depth`
+    depth_exceeded_symbol: &'a str,
+    /// Per node replacement of the `node_closed_symbol`
+    node_closed_symbol_fn: Option<NodeSymbolFn<'a, Identifier>>,
+    /// Per node replacement of the `node_open_symbol`
+    node_open_symbol_fn: Option<NodeSymbolFn<'a, Identifier>>,
 }
 
 impl<'a, Identifier> Tree<'a, Identifier>
@@ -82,16 +144,13 @@ where
     /// # Errors
     ///
     /// Errors when there are duplicate identifiers in the children.
-    pub fn new(items: &'a [TreeItem<'a, Identifier>]) -> std::io::Result<Self> {
+    pub fn new(items: &'a [TreeItem<'a, Identifier>]) -> Result<Self, TreeItemError> {
         let identifiers = items
             .iter()
             .map(|item| &item.identifier)
             .collect::<HashSet<_>>();
         if identifiers.len() != items.len() {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::AlreadyExists,
-                "The items contain duplicate identifiers",
-            ));
+            return Err(TreeItemError::DuplicateIdentifiers);
         }
 
         Ok(Self {
@@ -99,11 +158,36 @@ where
             block: None,
             scrollbar: None,
             style: Style::new(),
+            empty_message: None,
+            empty_style: Style::new(),
+            item_style: Style::new(),
+            zebra_striping: None,
+            #[cfg(feature = "animation")]
+            scroll_animation_speed: 0,
+            line_numbers: false,
+            line_numbers_base: 1,
+            line_number_style: Style::new().fg(Color::DarkGray),
             highlight_style: Style::new(),
+            focused: true,
+            unfocused_highlight_style: Style::new().add_modifier(Modifier::REVERSED),
             highlight_symbol: "",
+            highlight_symbol_reserved_width: None,
+            marked_style: Style::new(),
+            separator_style: Style::new(),
+            top_level_separator: None,
+            gap_between_top_level_items: 0,
+            indent_width: 2,
+            node_indent_guide: None,
+            node_indent_guide_style: Style::new(),
             node_closed_symbol: "\u{25b6} ", // Arrow to right
             node_open_symbol: "\u{25bc} ",   // Arrow down
             node_no_children_symbol: "  ",
+            node_closed_symbols: Vec::new(),
+            node_open_symbols: Vec::new(),
+            max_depth: None,
+            depth_exceeded_symbol: "\u{2026} ", // Ellipsis
+            node_closed_symbol_fn: None,
+            node_open_symbol_fn: None,
         })
     }
 
@@ -123,21 +207,201 @@ where
         self
     }
 
+    /// The configured scrollbar or a new one on the right side to be customized.
+    const fn take_scrollbar(&mut self) -> Scrollbar<'a> {
+        match self.scrollbar.take() {
+            Some(scrollbar) => scrollbar,
+            None => Scrollbar::new(ScrollbarOrientation::VerticalRight),
+        }
+    }
+
+    /// Set the symbol at the beginning of the scrollbar. `None` hides it.
+    ///
+    /// Shows the [`experimental_scrollbar`](Self::experimental_scrollbar) on the right side when none is configured yet.
+    pub const fn scrollbar_begin_symbol(mut self, symbol: Option<&'a str>) -> Self {
+        self.scrollbar = Some(self.take_scrollbar().begin_symbol(symbol));
+        self
+    }
+
+    /// Set the symbol at the end of the scrollbar. `None` hides it.
+    ///
+    /// Shows the [`experimental_scrollbar`](Self::experimental_scrollbar) on the right side when none is configured yet.
+    pub const fn scrollbar_end_symbol(mut self, symbol: Option<&'a str>) -> Self {
+        self.scrollbar = Some(self.take_scrollbar().end_symbol(symbol));
+        self
+    }
+
+    /// Set the symbol of the scrollbar track. `None` hides it.
+    ///
+    /// Shows the [`experimental_scrollbar`](Self::experimental_scrollbar) on the right side when none is configured yet.
+    pub const fn scrollbar_track_symbol(mut self, symbol: Option<&'a str>) -> Self {
+        self.scrollbar = Some(self.take_scrollbar().track_symbol(symbol));
+        self
+    }
+
+    /// Set the symbol of the scrollbar thumb.
+    ///
+    /// Shows the [`experimental_scrollbar`](Self::experimental_scrollbar) on the right side when none is configured yet.
+    pub const fn scrollbar_thumb_symbol(mut self, symbol: &'a str) -> Self {
+        self.scrollbar = Some(self.take_scrollbar().thumb_symbol(symbol));
+        self
+    }
+
     pub const fn style(mut self, style: Style) -> Self {
         self.style = style;
         self
     }
 
+    /// Show a placeholder centered in the widget when there are no items to render.
+    pub const fn empty_message(mut self, message: &'a str) -> Self {
+        self.empty_message = Some(message);
+        self
+    }
+
+    /// Style of the [`empty_message`](Self::empty_message) applied on top of the base [`style`](Self::style).
+    pub const fn empty_style(mut self, style: Style) -> Self {
+        self.empty_style = style;
+        self
+    }
+
+    /// Style of every rendered row except the selected one, for example to dim them.
+    ///
+    /// Applied on top of the [`zebra_striping`](Self::zebra_striping).
+    /// The style of the [`TreeItem`] text takes precedence.
+    pub const fn item_style(mut self, style: Style) -> Self {
+        self.item_style = style;
+        self
+    }
+
+    /// Alternate the style of the rendered rows.
+    ///
+    /// `even` is used for the first, third, … rendered row and `odd` for the second, fourth, ….
+    /// The position is based on the rendered output, not on the position in the data.
+    /// The [`highlight_style`](Self::highlight_style) of the selected row takes precedence.
+    pub const fn zebra_striping(mut self, even: Style, odd: Style) -> Self {
+        self.zebra_striping = Some((even, odd));
+        self
+    }
+
+    /// Smoothly scroll towards a new scroll position instead of jumping to it.
+    ///
+    /// Each render moves the view by at most `speed` rows towards the target.
+    /// As long as [`TreeState::is_scroll_animating`] returns `true` another render is needed to continue the animation.
+    /// A `speed` of 0 disables the animation (default).
+    #[cfg(feature = "animation")]
+    pub const fn animate_scroll(mut self, speed: u8) -> Self {
+        self.scroll_animation_speed = speed;
+        self
+    }
+
+    /// Show the index of each row in front of it.
+    ///
+    /// The numbers are right-aligned in a column as wide as the biggest number.
+    /// Helpful for debugging the navigation.
+    pub const fn line_numbers(mut self, enabled: bool) -> Self {
+        self.line_numbers = enabled;
+        self
+    }
+
+    /// Number shown for the first row of the [`line_numbers`](Self::line_numbers). Defaults to 1.
+    pub const fn line_numbers_base(mut self, base: usize) -> Self {
+        self.line_numbers_base = base;
+        self
+    }
+
+    /// Style of the [`line_numbers`](Self::line_numbers). Defaults to [`Color::DarkGray`].
+    pub const fn line_number_style(mut self, style: Style) -> Self {
+        self.line_number_style = style;
+        self
+    }
+
     pub const fn highlight_style(mut self, style: Style) -> Self {
         self.highlight_style = style;
         self
     }
 
+    /// Whether the widget currently has the focus, for example in a layout with multiple panels. Defaults to `true`.
+    ///
+    /// When not focused, the selected row uses the [`unfocused_highlight_style`](Self::unfocused_highlight_style) instead of the [`highlight_style`](Self::highlight_style).
+    pub const fn focused(mut self, is_focused: bool) -> Self {
+        self.focused = is_focused;
+        self
+    }
+
+    /// Style of the selected row while the widget is not [`focused`](Self::focused).
+    /// Defaults to [`Modifier::REVERSED`].
+    pub const fn unfocused_highlight_style(mut self, style: Style) -> Self {
+        self.unfocused_highlight_style = style;
+        self
+    }
+
     pub const fn highlight_symbol(mut self, highlight_symbol: &'a str) -> Self {
         self.highlight_symbol = highlight_symbol;
         self
     }
 
+    /// Reserve the given amount of columns for the [`highlight_symbol`](Self::highlight_symbol) instead of its width.
+    ///
+    /// Helpful when terminals display some Unicode symbols with a different width than expected.
+    /// A symbol wider than the reserved columns is cut off.
+    pub const fn highlight_symbol_reserved_width(mut self, width: u16) -> Self {
+        self.highlight_symbol_reserved_width = Some(width);
+        self
+    }
+
+    /// Style of nodes marked with [`TreeState::mark`].
+    ///
+    /// Applied after the [`highlight_style`](Self::highlight_style) of the selected row.
+    pub const fn marked_style(mut self, style: Style) -> Self {
+        self.marked_style = style;
+        self
+    }
+
+    /// Style of the line rendered for [`TreeItem::new_separator`] items.
+    pub const fn separator_style(mut self, style: Style) -> Self {
+        self.separator_style = style;
+        self
+    }
+
+    /// Fill a row between top level nodes with the given character, for example `'─'`.
+    ///
+    /// The row is rendered with the base [`style`](Self::style) in front of each top level node except the first one.
+    /// Use [`TreeItem::new_separator`] for separators in other places.
+    pub const fn top_level_separator(mut self, separator: char) -> Self {
+        self.top_level_separator = Some(separator);
+        self
+    }
+
+    /// Leave the given amount of empty rows between top level nodes. Defaults to 0.
+    ///
+    /// Like the [`top_level_separator`](Self::top_level_separator) the rows are in front of each top level node except the first one.
+    /// When both are used, the separator follows the empty rows.
+    pub const fn gap_between_top_level_items(mut self, gap: u16) -> Self {
+        self.gap_between_top_level_items = gap;
+        self
+    }
+
+    /// Amount of spaces to indent each depth level with. Defaults to 2.
+    pub const fn indent_width(mut self, spaces: u16) -> Self {
+        self.indent_width = spaces;
+        self
+    }
+
+    /// Render a guide like `"│"` in the indentation to visually connect the children of a node.
+    ///
+    /// The guide is shown at the indentation of each ancestor whose children continue below.
+    /// It is limited to the [`indent_width`](Self::indent_width).
+    pub const fn node_indent_guide(mut self, symbol: &'a str) -> Self {
+        self.node_indent_guide = Some(symbol);
+        self
+    }
+
+    /// Style of the [`node_indent_guide`](Self::node_indent_guide).
+    pub const fn node_indent_guide_style(mut self, style: Style) -> Self {
+        self.node_indent_guide_style = style;
+        self
+    }
+
     pub const fn node_closed_symbol(mut self, symbol: &'a str) -> Self {
         self.node_closed_symbol = symbol;
         self
@@ -152,10 +416,241 @@ where
         self.node_no_children_symbol = symbol;
         self
     }
+
+    /// Use a different symbol in front of closed nodes depending on their depth.
+    ///
+    /// The symbol at the index of the depth is used. Deeper nodes use the last symbol.
+    /// Takes precedence over the [`node_closed_symbol`](Self::node_closed_symbol) unless empty.
+    ///
+    /// ```
+    /// # use tui_tree_widget::{Tree, TreeItem};
+    /// # let items = vec![TreeItem::new_leaf("a", "Alfa")];
+    /// let tree = Tree::new(&items)?.node_closed_symbols(vec!["\u{25b6} ", "\u{25b8} ", "\u{203a} "]);
+    /// # Ok::<(), tui_tree_widget::TreeItemError>(())
+    /// ```
+    pub fn node_closed_symbols(mut self, symbols: Vec<&'a str>) -> Self {
+        self.node_closed_symbols = symbols;
+        self
+    }
+
+    /// Use a different symbol in front of open nodes depending on their depth.
+    ///
+    /// The symbol at the index of the depth is used. Deeper nodes use the last symbol.
+    /// Takes precedence over the [`node_open_symbol`](Self::node_open_symbol) unless empty.
+    pub fn node_open_symbols(mut self, symbols: Vec<&'a str>) -> Self {
+        self.node_open_symbols = symbols;
+        self
+    }
+
+    /// Only render nodes up to the given amount of levels even when deeper nodes are open.
+    ///
+    /// Helpful for very deep trees.
+    /// Nodes with children beyond this depth show the [`depth_exceeded_symbol`](Self::depth_exceeded_symbol).
+    /// These deeper nodes are not drawn and can not be selected but stay part of the [`TreeState`] like its [`visible_node_count`](TreeState::visible_node_count).
+    pub const fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Symbol displayed in front of a node whose children are not rendered due to the [`max_depth`](Self::max_depth).
+    /// Defaults to `"… "`.
+    pub const fn depth_exceeded_symbol(mut self, symbol: &'a str) -> Self {
+        self.depth_exceeded_symbol = symbol;
+        self
+    }
+
+    /// Decide the symbol of each closed node individually, for example based on its identifier.
+    ///
+    /// Takes precedence over the [`node_closed_symbol`](Self::node_closed_symbol).
+    ///
+    /// ```
+    /// # use tui_tree_widget::{Tree, TreeItem};
+    /// # let items = vec![TreeItem::new_leaf("src/", "src")];
+    /// let tree = Tree::new(&items)?.node_closed_symbol_fn(|flattened| {
+    ///     if flattened
+    ///         .identifier
+    ///         .last()
+    ///         .is_some_and(|name| name.ends_with('/'))
+    ///     {
+    ///         "+ "
+    ///     } else {
+    ///         "> "
+    ///     }
+    /// });
+    /// # Ok::<(), tui_tree_widget::TreeItemError>(())
+    /// ```
+    pub fn node_closed_symbol_fn<F>(mut self, symbol: F) -> Self
+    where
+        F: Fn(&Flattened<Identifier>) -> &'a str + 'a,
+    {
+        self.node_closed_symbol_fn = Some(NodeSymbolFn(Rc::new(symbol)));
+        self
+    }
+
+    /// Decide the symbol of each open node individually, for example based on its identifier.
+    ///
+    /// Takes precedence over the [`node_open_symbol`](Self::node_open_symbol).
+    pub fn node_open_symbol_fn<F>(mut self, symbol: F) -> Self
+    where
+        F: Fn(&Flattened<Identifier>) -> &'a str + 'a,
+    {
+        self.node_open_symbol_fn = Some(NodeSymbolFn(Rc::new(symbol)));
+        self
+    }
+
+    /// Render into a new buffer of the given size and return its content as plain text.
+    ///
+    /// Lines are separated by `\n` and trailing whitespace is removed from each line.
+    /// Styles are ignored.
+    /// Helpful for snapshot tests.
+    ///
+    /// ```
+    /// # use tui_tree_widget::{Tree, TreeItem, TreeState};
+    /// let items = [
+    ///     TreeItem::new_leaf("a", "Alfa"),
+    ///     TreeItem::new_leaf("b", "Bravo"),
+    /// ];
+    /// let mut state = TreeState::default();
+    /// let text = Tree::new(&items)?.render_to_string(10, 2, &mut state);
+    /// assert_eq!(text, "  Alfa\n  Bravo\n");
+    /// # Ok::<(), tui_tree_widget::TreeItemError>(())
+    /// ```
+    #[must_use]
+    pub fn render_to_string(
+        self,
+        width: u16,
+        height: u16,
+        state: &mut TreeState<Identifier>,
+    ) -> String {
+        let area = Rect::new(0, 0, width, height);
+        let mut buffer = Buffer::empty(area);
+        self.render(area, &mut buffer, state);
+
+        let mut result = String::new();
+        for row in buffer.content.chunks(usize::from(width).max(1)) {
+            let mut line = String::new();
+            let mut skip = 0;
+            for cell in row {
+                if skip > 0 {
+                    skip -= 1;
+                    continue;
+                }
+                let symbol = cell.symbol();
+                skip = symbol.width().saturating_sub(1);
+                line += symbol;
+            }
+            result += line.trim_end();
+            result.push('\n');
+        }
+        result
+    }
+}
+
+impl<'a, Identifier> TryFrom<&'a [TreeItem<'a, Identifier>]> for Tree<'a, Identifier>
+where
+    Identifier: Clone + PartialEq + Eq + core::hash::Hash,
+{
+    type Error = TreeItemError;
+
+    /// Same as [`Tree::new`].
+    fn try_from(items: &'a [TreeItem<'a, Identifier>]) -> Result<Self, Self::Error> {
+        Self::new(items)
+    }
+}
+
+/// A `Tree` without any items.
+impl<Identifier> Default for Tree<'_, Identifier>
+where
+    Identifier: Clone + PartialEq + Eq + core::hash::Hash,
+{
+    fn default() -> Self {
+        Self::new(&[]).expect("no items can not have duplicate identifiers")
+    }
+}
+
+// Not generic over the Identifier so `Tree::popup` can be called without specifying one.
+impl Tree<'static, ()> {
+    /// Calculate the area of a popup showing a [`Tree`] anchored at `anchor`.
+    ///
+    /// The popup is placed below the `anchor` when there is enough space for `max_height` or more space than above.
+    /// Otherwise, it is placed above the `anchor`.
+    /// The result is limited to the given `area`, which is typically the full frame area.
+    ///
+    /// Ratatui renders widgets in the order they are drawn, so render the popup last to be on top.
+    /// Consider rendering [`Clear`](ratatui::widgets::Clear) first to remove what is below the popup.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ratatui::layout::Rect;
+    /// # use tui_tree_widget::Tree;
+    /// let area = Rect::new(0, 0, 80, 24);
+    /// let input_field = Rect::new(10, 2, 20, 1);
+    /// let popup = Tree::popup(area, input_field, 30, 10);
+    /// assert_eq!(popup, Rect::new(10, 3, 30, 10));
+    /// ```
+    #[must_use]
+    pub fn popup(area: Rect, anchor: Rect, preferred_width: u16, max_height: u16) -> Rect {
+        let width = preferred_width.min(area.width);
+        let x = anchor.x.min(area.right().saturating_sub(width)).max(area.x);
+
+        let below = anchor.bottom().clamp(area.y, area.bottom());
+        let above = anchor.y.clamp(area.y, area.bottom());
+        let space_below = area.bottom() - below;
+        let space_above = above - area.y;
+
+        if space_below >= max_height || space_below >= space_above {
+            Rect::new(x, below, width, max_height.min(space_below))
+        } else {
+            let height = max_height.min(space_above);
+            Rect::new(x, above - height, width, height)
+        }
+    }
+}
+
+#[test]
+fn popup_prefers_below_anchor() {
+    let area = Rect::new(0, 0, 40, 20);
+    let anchor = Rect::new(5, 3, 10, 1);
+    assert_eq!(Tree::popup(area, anchor, 20, 8), Rect::new(5, 4, 20, 8));
+}
+
+#[test]
+fn popup_above_anchor_when_not_enough_space_below() {
+    let area = Rect::new(0, 0, 40, 20);
+    let anchor = Rect::new(5, 16, 10, 1);
+    assert_eq!(Tree::popup(area, anchor, 20, 8), Rect::new(5, 8, 20, 8));
+}
+
+#[test]
+fn popup_stays_inside_area() {
+    let area = Rect::new(0, 0, 40, 10);
+    let anchor = Rect::new(30, 4, 10, 1);
+    assert_eq!(Tree::popup(area, anchor, 20, 8), Rect::new(20, 5, 20, 5));
+    assert_eq!(Tree::popup(area, anchor, 50, 3), Rect::new(0, 5, 40, 3));
+}
+
+#[test]
+fn tree_try_from_works() {
+    let items = TreeItem::example();
+    let tree = Tree::try_from(items.as_slice()).unwrap();
+    assert_eq!(tree.items.len(), 3);
+
+    let duplicates = [
+        TreeItem::new_leaf("a", "Alfa"),
+        TreeItem::new_leaf("a", "Alfa"),
+    ];
+    assert!(Tree::try_from(duplicates.as_slice()).is_err());
+}
+
+#[test]
+fn tree_default_is_empty() {
+    let tree = Tree::<usize>::default();
+    assert!(tree.items.is_empty());
 }
 
 #[test]
-#[should_panic = "duplicate identifiers"]
+#[should_panic = "DuplicateIdentifiers"]
 fn tree_new_errors_with_duplicate_identifiers() {
     let item = TreeItem::new_leaf("same", "text");
     let another = item.clone();
@@ -182,16 +677,69 @@ where
 
         state.last_area = area;
         state.last_rendered_identifiers.clear();
+        state.last_nodes.clear();
+        state.last_total_height = 0;
+        state.last_heights.clear();
+        state.last_rendered_range = 0..0;
         if area.width < 1 || area.height < 1 {
             return;
         }
 
         let visible = state.flatten(self.items);
+        // Nodes beyond the max_depth are not drawn but stay part of the state
+        let is_hidden = |flattened: &Flattened<Identifier>| {
+            self.max_depth
+                .is_some_and(|max_depth| flattened.depth() >= max_depth)
+        };
         state.last_biggest_index = visible.len().saturating_sub(1);
+        state.last_nodes = visible
+            .iter()
+            .map(|flattened| Node {
+                depth: flattened.depth(),
+                has_children: !flattened.is_leaf(),
+                is_separator: flattened.item.separator,
+                is_hidden: is_hidden(flattened),
+                height: flattened.item.height(),
+            })
+            .collect();
+        state.last_max_content_width = visible
+            .iter()
+            .filter(|flattened| !is_hidden(flattened))
+            .map(|flattened| flattened.item.text.width())
+            .max()
+            .unwrap_or_default();
         if visible.is_empty() {
+            if let Some(message) = self.empty_message {
+                let width = u16::try_from(message.width()).unwrap_or(u16::MAX);
+                let x = area.x + area.width.saturating_sub(width) / 2;
+                let y = area.y + area.height / 2;
+                buf.set_stringn(x, y, message, area.width as usize, self.empty_style);
+            }
             return;
         }
         let available_height = area.height as usize;
+        // Additional rows rendered in front of the item at the index
+        let rows_before = |index: usize, flattened: &Flattened<Identifier>| {
+            if index > 0 && flattened.is_root() {
+                usize::from(self.gap_between_top_level_items)
+                    + usize::from(self.top_level_separator.is_some())
+            } else {
+                0
+            }
+        };
+        let heights = visible
+            .iter()
+            .enumerate()
+            .map(|(index, flattened)| {
+                if is_hidden(flattened) {
+                    0
+                } else {
+                    rows_before(index, flattened) + flattened.item.height()
+                }
+            })
+            .collect::<Vec<_>>();
+        state.last_total_height = heights.iter().sum();
+        state.last_heights.clone_from(&heights);
 
         let ensure_index_in_view =
             if state.ensure_selected_in_view_on_next_render && !state.selected.is_empty() {
@@ -209,26 +757,14 @@ where
             start = start.min(ensure_index_in_view);
         }
 
-        let mut end = start;
-        let mut height = 0;
-        for item_height in visible
-            .iter()
-            .skip(start)
-            .map(|flattened| flattened.item.height())
-        {
-            if height + item_height > available_height {
-                break;
-            }
-            height += item_height;
-            end += 1;
-        }
+        let (mut end, mut height) = fitting_end(&heights, start, available_height);
 
         if let Some(ensure_index_in_view) = ensure_index_in_view {
             while ensure_index_in_view >= end {
-                height += visible[end].item.height();
+                height += heights[end];
                 end += 1;
                 while height > available_height {
-                    height = height.saturating_sub(visible[start].item.height());
+                    height = height.saturating_sub(heights[start]);
                     start += 1;
                 }
             }
@@ -237,10 +773,26 @@ where
         state.offset = start;
         state.ensure_selected_in_view_on_next_render = false;
 
+        #[cfg(feature = "animation")]
+        {
+            if self.scroll_animation_speed > 0 {
+                let speed = usize::from(self.scroll_animation_speed);
+                let previous = state.animated_offset.min(state.last_biggest_index);
+                start = if previous < start {
+                    previous.saturating_add(speed).min(start)
+                } else {
+                    previous.saturating_sub(speed).max(start)
+                };
+                (end, height) = fitting_end(&heights, start, available_height);
+            }
+            state.animated_offset = start;
+        }
+
         if let Some(scrollbar) = self.scrollbar {
-            let mut scrollbar_state = ScrollbarState::new(visible.len().saturating_sub(height))
-                .position(start)
-                .viewport_content_length(height);
+            let mut scrollbar_state =
+                ScrollbarState::new(state.last_total_height.saturating_sub(height))
+                    .position(heights[..start].iter().sum())
+                    .viewport_content_length(height);
             let scrollbar_area = Rect {
                 // Inner height to be exactly as the content
                 y: area.y,
@@ -252,14 +804,60 @@ where
             scrollbar.render(scrollbar_area, buf, &mut scrollbar_state);
         }
 
-        let blank_symbol = " ".repeat(self.highlight_symbol.width());
+        let highlight_symbol_width = self
+            .highlight_symbol_reserved_width
+            .map_or_else(|| self.highlight_symbol.width(), usize::from);
+        let blank_symbol = " ".repeat(highlight_symbol_width);
+        // Only the drawn nodes are numbered
+        let mut drawn_count = 0;
+        let line_numbers = visible
+            .iter()
+            .map(|flattened| {
+                let number = drawn_count + self.line_numbers_base;
+                if !is_hidden(flattened) {
+                    drawn_count += 1;
+                }
+                number
+            })
+            .collect::<Vec<_>>();
+        let line_number_width = (drawn_count.saturating_sub(1) + self.line_numbers_base)
+            .to_string()
+            .len();
+        let indent_guides = if self.node_indent_guide.is_some() {
+            indent_guides(&visible)
+        } else {
+            Vec::new()
+        };
 
+        state.last_rendered_range = start..end;
         let mut current_height = 0;
         let has_selection = !state.selected.is_empty();
         #[allow(clippy::cast_possible_truncation)]
-        for flattened in visible.iter().skip(state.offset).take(end - start) {
+        for (row, (index, flattened)) in visible
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(end - start)
+            .filter(|(_, flattened)| !is_hidden(flattened))
+            .enumerate()
+        {
             let Flattened { identifier, item } = flattened;
 
+            if rows_before(index, flattened) > 0 {
+                current_height += self.gap_between_top_level_items;
+                if let Some(separator) = self.top_level_separator {
+                    let width = area.width as usize;
+                    buf.set_stringn(
+                        area.x,
+                        area.y + current_height,
+                        String::from(separator).repeat(width),
+                        width,
+                        self.style,
+                    );
+                    current_height += 1;
+                }
+            }
+
             let x = area.x;
             let y = area.y + current_height;
             let height = item.height() as u16;
@@ -272,37 +870,105 @@ where
                 height,
             };
 
+            let is_selected = state.selected == *identifier;
+
+            if let Some((even, odd)) = self.zebra_striping {
+                buf.set_style(area, if row % 2 == 0 { even } else { odd });
+            }
+            if !is_selected {
+                buf.set_style(area, self.item_style);
+            }
+
             let text = &item.text;
             let item_style = text.style;
 
-            let is_selected = state.selected == *identifier;
-            let after_highlight_symbol_x = if has_selection {
-                let symbol = if is_selected {
-                    self.highlight_symbol
-                } else {
-                    &blank_symbol
-                };
-                let (x, _) = buf.set_stringn(x, y, symbol, area.width as usize, item_style);
+            let after_line_number_x = if self.line_numbers {
+                let number = format!("{:>line_number_width$} ", line_numbers[index]);
+                let (x, _) =
+                    buf.set_stringn(x, y, number, area.width as usize, self.line_number_style);
                 x
             } else {
                 x
             };
 
-            let after_depth_x = {
-                let indent_width = flattened.depth() * 2;
-                let (after_indent_x, _) = buf.set_stringn(
+            // Every line of multi-line items gets the highlight symbol and indentation
+            let mut after_highlight_symbol_x = after_line_number_x;
+            let mut after_indent_x = after_line_number_x;
+            for line_y in y..y + height {
+                if has_selection {
+                    let symbol = if is_selected {
+                        self.highlight_symbol
+                    } else {
+                        &blank_symbol
+                    };
+                    let max_width = area.width.saturating_sub(after_line_number_x - x);
+                    let max_width = (max_width as usize).min(highlight_symbol_width);
+                    buf.set_stringn(after_line_number_x, line_y, symbol, max_width, item_style);
+                    after_highlight_symbol_x = after_line_number_x + max_width as u16;
+                }
+
+                let indent_width = flattened.depth() * usize::from(self.indent_width);
+                (after_indent_x, _) = buf.set_stringn(
                     after_highlight_symbol_x,
-                    y,
+                    line_y,
                     " ".repeat(indent_width),
                     indent_width,
                     item_style,
                 );
+                if let Some(guide) = self.node_indent_guide {
+                    let guide_width = usize::from(self.indent_width);
+                    for (depth, _) in indent_guides[index]
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, has_guide)| **has_guide)
+                    {
+                        let guide_x = after_highlight_symbol_x as usize + depth * guide_width;
+                        let max_width = (area.right() as usize)
+                            .saturating_sub(guide_x)
+                            .min(guide_width);
+                        if max_width > 0 {
+                            buf.set_stringn(
+                                guide_x as u16,
+                                line_y,
+                                guide,
+                                max_width,
+                                self.node_indent_guide_style,
+                            );
+                        }
+                    }
+                }
+            }
+
+            let after_depth_x = {
                 let symbol = if item.children.is_empty() {
                     self.node_no_children_symbol
+                } else if self
+                    .max_depth
+                    .is_some_and(|max_depth| flattened.depth() + 1 >= max_depth)
+                {
+                    self.depth_exceeded_symbol
                 } else if state.opened.contains(identifier) {
-                    self.node_open_symbol
+                    self.node_open_symbol_fn.as_ref().map_or_else(
+                        || {
+                            symbol_for_depth(
+                                &self.node_open_symbols,
+                                flattened.depth(),
+                                self.node_open_symbol,
+                            )
+                        },
+                        |symbol| (symbol.0)(flattened),
+                    )
                 } else {
-                    self.node_closed_symbol
+                    self.node_closed_symbol_fn.as_ref().map_or_else(
+                        || {
+                            symbol_for_depth(
+                                &self.node_closed_symbols,
+                                flattened.depth(),
+                                self.node_closed_symbol,
+                            )
+                        },
+                        |symbol| (symbol.0)(flattened),
+                    )
                 };
                 let max_width = area.width.saturating_sub(after_indent_x - x);
                 let (x, _) =
@@ -315,15 +981,41 @@ where
                 width: area.width.saturating_sub(after_depth_x - x),
                 ..area
             };
-            text.render(text_area, buf);
+            if item.separator {
+                let width = text_area.width as usize;
+                buf.set_stringn(
+                    text_area.x,
+                    text_area.y,
+                    "\u{2500}".repeat(width),
+                    width,
+                    self.separator_style,
+                );
+            } else if state.x_offset > 0 {
+                buf.set_style(text_area, text.style);
+                Paragraph::new(text.clone())
+                    .scroll((0, state.x_offset.try_into().unwrap_or(u16::MAX)))
+                    .render(text_area, buf);
+            } else {
+                text.render(text_area, buf);
+            }
+
+            buf.set_style(area, item.row_style);
 
             if is_selected {
-                buf.set_style(area, self.highlight_style);
+                let style = if self.focused {
+                    self.highlight_style
+                } else {
+                    self.unfocused_highlight_style
+                };
+                buf.set_style(area, style);
+            }
+            if state.marked.contains(identifier) {
+                buf.set_style(area, self.marked_style);
             }
 
             state
                 .last_rendered_identifiers
-                .push((area.y, identifier.clone()));
+                .push((area.y..area.bottom(), identifier.clone()));
         }
         state.last_identifiers = visible
             .into_iter()
@@ -332,6 +1024,69 @@ where
     }
 }
 
+/// For each visible item the ancestor depths at which an indent guide is shown.
+///
+/// A guide is shown for the direct parent and for each ancestor whose children continue below the item.
+fn indent_guides<Identifier>(visible: &[Flattened<'_, Identifier>]) -> Vec<Vec<bool>> {
+    let mut guides = vec![Vec::new(); visible.len()];
+    // Whether a later item exists at the depth without leaving the parent first
+    let mut has_following = Vec::new();
+    for (index, flattened) in visible.iter().enumerate().rev() {
+        let depth = flattened.depth();
+        guides[index] = (0..depth)
+            .map(|ancestor| {
+                ancestor + 1 == depth || has_following.get(ancestor + 1).copied().unwrap_or(false)
+            })
+            .collect();
+        has_following.truncate(depth);
+        has_following.resize(depth + 1, false);
+        has_following[depth] = true;
+    }
+    guides
+}
+
+/// Closure deciding the symbol of a node. See [`Tree::node_closed_symbol_fn`].
+///
+/// Wrapped to implement [`Clone`] and [`Debug`](core::fmt::Debug) which closures do not.
+struct NodeSymbolFn<'a, Identifier>(Rc<SymbolFn<'a, Identifier>>);
+
+type SymbolFn<'a, Identifier> = dyn Fn(&Flattened<Identifier>) -> &'a str + 'a;
+
+impl<Identifier> Clone for NodeSymbolFn<'_, Identifier> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<Identifier> core::fmt::Debug for NodeSymbolFn<'_, Identifier> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("NodeSymbolFn")
+    }
+}
+
+/// Get the symbol for the depth or the last one for deeper levels. Uses the `fallback` when there are no `symbols`.
+fn symbol_for_depth<'a>(symbols: &[&'a str], depth: usize, fallback: &'a str) -> &'a str {
+    symbols
+        .get(depth)
+        .or_else(|| symbols.last())
+        .copied()
+        .unwrap_or(fallback)
+}
+
+/// Get the exclusive end index and the used height when rendering items with the given `heights` beginning with `start`.
+fn fitting_end(heights: &[usize], start: usize, available_height: usize) -> (usize, usize) {
+    let mut end = start;
+    let mut height = 0;
+    for &item_height in heights.iter().skip(start) {
+        if height + item_height > available_height {
+            break;
+        }
+        height += item_height;
+        end += 1;
+    }
+    (end, height)
+}
+
 impl<Identifier> Widget for Tree<'_, Identifier>
 where
     Identifier: Clone + Default + Eq + core::hash::Hash,
@@ -351,6 +1106,17 @@ mod render_tests {
     fn render(width: u16, height: u16, state: &mut TreeState<&'static str>) -> Buffer {
         let items = TreeItem::example();
         let tree = Tree::new(&items).unwrap();
+        render_tree(tree, width, height, state)
+    }
+
+    #[must_use]
+    #[track_caller]
+    fn render_tree(
+        tree: Tree<&'static str>,
+        width: u16,
+        height: u16,
+        state: &mut TreeState<&'static str>,
+    ) -> Buffer {
         let area = Rect::new(0, 0, width, height);
         let mut buffer = Buffer::empty(area);
         StatefulWidget::render(tree, area, &mut buffer, state);
@@ -414,4 +1180,520 @@ mod render_tests {
         ]);
         assert_eq!(buffer, expected);
     }
+
+    #[test]
+    fn zebra_striping() {
+        use ratatui::style::Color;
+
+        let items = TreeItem::example();
+        let even = Style::new().bg(Color::Black);
+        let odd = Style::new().bg(Color::DarkGray);
+        let highlight = Style::new().bg(Color::Blue);
+        let tree = Tree::new(&items)
+            .unwrap()
+            .zebra_striping(even, odd)
+            .highlight_style(highlight);
+        let mut state = TreeState::default();
+        state.select(vec!["h"]);
+        let buffer = render_tree(tree, 10, 4, &mut state);
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "  Alfa    ",
+            "▶ Bravo   ",
+            "  Hotel   ",
+            "          ",
+        ]);
+        expected.set_style(Rect::new(0, 0, 10, 1), even);
+        expected.set_style(Rect::new(0, 1, 10, 1), odd);
+        expected.set_style(Rect::new(0, 2, 10, 1), highlight);
+        assert_eq!(buffer, expected);
+    }
+
+    #[cfg(feature = "animation")]
+    #[test]
+    fn animate_scroll() {
+        let items = TreeItem::example();
+        let mut state = TreeState::default();
+        state.open(vec!["b"]);
+        state.open(vec!["b", "d"]);
+        _ = render_tree(Tree::new(&items).unwrap(), 10, 3, &mut state);
+        assert!(!state.is_scroll_animating());
+
+        state.scroll_down(4);
+        let tree = Tree::new(&items).unwrap().animate_scroll(3);
+        let buffer = render_tree(tree.clone(), 10, 3, &mut state);
+        let expected = Buffer::with_lines(["  ▼ Delta ", "      Echo", "      Foxt"]);
+        assert_eq!(buffer, expected);
+        assert!(state.is_scroll_animating());
+
+        let buffer = render_tree(tree, 10, 3, &mut state);
+        let expected = Buffer::with_lines(["      Echo", "      Foxt", "    Golf  "]);
+        assert_eq!(buffer, expected);
+        assert!(!state.is_scroll_animating());
+    }
+
+    #[test]
+    fn separator() {
+        let items = [
+            TreeItem::new_leaf("a", "Alfa"),
+            TreeItem::new_separator("s"),
+            TreeItem::new_leaf("b", "Bravo"),
+        ];
+        let tree = Tree::new(&items).unwrap();
+        let area = Rect::new(0, 0, 8, 3);
+        let mut buffer = Buffer::empty(area);
+        let mut state = TreeState::default();
+        StatefulWidget::render(tree, area, &mut buffer, &mut state);
+        let expected = Buffer::with_lines(["  Alfa  ", "  ──────", "  Bravo "]);
+        assert_eq!(buffer, expected);
+
+        assert!(state.key_down());
+        assert_eq!(state.selected(), ["a"]);
+        assert!(state.key_down());
+        assert_eq!(state.selected(), ["b"]);
+        assert!(!state.key_down());
+        assert!(state.key_up());
+        assert_eq!(state.selected(), ["a"]);
+    }
+
+    #[test]
+    fn separator_is_not_selectable() {
+        let items = [
+            TreeItem::new_separator("s1"),
+            TreeItem::new_leaf("a", "Alfa"),
+            TreeItem::new_separator("s2"),
+            TreeItem::new_leaf("b", "Bravo"),
+            TreeItem::new_separator("s3"),
+        ];
+        let mut state = TreeState::default();
+        let tree = Tree::new(&items).unwrap();
+        _ = render_tree(tree, 8, 5, &mut state);
+
+        assert!(state.select_first());
+        assert_eq!(state.selected(), ["a"]);
+        assert!(state.select_last());
+        assert_eq!(state.selected(), ["b"]);
+        assert!(state.select_first_visible());
+        assert_eq!(state.selected(), ["a"]);
+        assert!(state.select_last_visible());
+        assert_eq!(state.selected(), ["b"]);
+
+        assert!(!state.click_at(ratatui::layout::Position::new(3, 0)));
+        assert!(!state.select_at_rendered_row(2));
+        assert_eq!(state.selected(), ["b"]);
+        assert!(state.click_at(ratatui::layout::Position::new(3, 1)));
+        assert_eq!(state.selected(), ["a"]);
+
+        assert!(state.page_down_selection());
+        assert_eq!(state.selected(), ["b"]);
+        assert!(state.page_up_selection());
+        assert_eq!(state.selected(), ["a"]);
+        assert!(state.select_relative(|_| 2));
+        assert_eq!(state.selected(), ["b"]);
+
+        assert!(state.find_and_select(|_| true));
+        assert_eq!(state.selected(), ["a"]);
+        assert!(state.select_next_where(|_| true));
+        assert_eq!(state.selected(), ["b"]);
+    }
+
+    #[test]
+    fn indent_width() {
+        let items = TreeItem::example();
+        let tree = Tree::new(&items).unwrap().indent_width(4);
+        let mut state = TreeState::default();
+        state.open(vec!["b"]);
+        state.open(vec!["b", "d"]);
+        let buffer = render_tree(tree, 19, 8, &mut state);
+        let expected = Buffer::with_lines([
+            "  Alfa             ",
+            "▼ Bravo            ",
+            "      Charlie      ",
+            "    ▼ Delta        ",
+            "          Echo     ",
+            "          Foxtrot  ",
+            "      Golf         ",
+            "  Hotel            ",
+        ]);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn node_indent_guide() {
+        let items = TreeItem::example();
+        let tree = Tree::new(&items).unwrap().node_indent_guide("│");
+        let mut state = TreeState::default();
+        state.open(vec!["b"]);
+        state.open(vec!["b", "d"]);
+        let buffer = render_tree(tree, 15, 8, &mut state);
+        let expected = Buffer::with_lines([
+            "  Alfa         ",
+            "▼ Bravo        ",
+            "│   Charlie    ",
+            "│ ▼ Delta      ",
+            "│ │   Echo     ",
+            "│ │   Foxtrot  ",
+            "│   Golf       ",
+            "  Hotel        ",
+        ]);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn node_indent_guide_ends_with_last_child() {
+        let items = [TreeItem::new(
+            "a",
+            "Alfa",
+            vec![TreeItem::new("b", "Bravo", vec![TreeItem::new_leaf("c", "Charlie")]).unwrap()],
+        )
+        .unwrap()];
+        let tree = Tree::new(&items).unwrap().node_indent_guide("│");
+        let mut state = TreeState::default();
+        state.open(vec!["a"]);
+        state.open(vec!["a", "b"]);
+        let buffer = render_tree(tree, 13, 3, &mut state);
+        let expected = Buffer::with_lines(["▼ Alfa       ", "│ ▼ Bravo    ", "  │   Charlie"]);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn x_offset() {
+        let mut state = TreeState::default();
+        state.open(vec!["b"]);
+        _ = render(10, 4, &mut state);
+        state.scroll_right(2);
+        let buffer = render(10, 4, &mut state);
+        let expected = Buffer::with_lines(["  fa      ", "▼ avo     ", "    arlie ", "  ▶ lta   "]);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn line_numbers() {
+        let items = TreeItem::example();
+        let tree = Tree::new(&items)
+            .unwrap()
+            .line_numbers(true)
+            .line_numbers_base(9)
+            .line_number_style(Style::new());
+        let mut state = TreeState::default();
+        state.open(vec!["b"]);
+        let buffer = render_tree(tree, 14, 4, &mut state);
+        let expected = Buffer::with_lines([
+            " 9   Alfa     ",
+            "10 ▼ Bravo    ",
+            "11     Charlie",
+            "12   ▶ Delta  ",
+        ]);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn item_style() {
+        let items = TreeItem::example();
+        let dimmed = Style::new().fg(Color::DarkGray);
+        let tree = Tree::new(&items).unwrap().item_style(dimmed);
+        let mut state = TreeState::default();
+        state.select(vec!["b"]);
+        let buffer = render_tree(tree, 7, 3, &mut state);
+        let mut expected = Buffer::with_lines(["  Alfa ", "▶ Bravo", "  Hotel"]);
+        expected.set_style(Rect::new(0, 0, 7, 1), dimmed);
+        expected.set_style(Rect::new(0, 2, 7, 1), dimmed);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn node_symbol_fn() {
+        let items = TreeItem::example();
+        let tree = Tree::new(&items)
+            .unwrap()
+            .node_open_symbol_fn(|flattened| if flattened.is_root() { "v " } else { "- " })
+            .node_closed_symbol_fn(|flattened| if flattened.is_root() { "> " } else { "+ " });
+        let mut state = TreeState::default();
+        state.open(vec!["b"]);
+        let buffer = render_tree(tree, 9, 4, &mut state);
+        let expected = Buffer::with_lines(["  Alfa   ", "v Bravo  ", "    Charl", "  + Delta"]);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn node_symbol_fn_captures_state() {
+        let items = TreeItem::example();
+        let links = HashSet::from([vec!["b"]]);
+        let tree = Tree::new(&items)
+            .unwrap()
+            .node_closed_symbol_fn(move |flattened| {
+                if links.contains(&flattened.identifier) {
+                    "@ "
+                } else {
+                    "> "
+                }
+            });
+        let buffer = render_tree(tree, 9, 3, &mut TreeState::default());
+        let expected = Buffer::with_lines(["  Alfa   ", "@ Bravo  ", "  Hotel  "]);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn top_level_separator() {
+        let items = TreeItem::example();
+        let tree = Tree::new(&items).unwrap().top_level_separator('-');
+        let mut state = TreeState::default();
+        state.open(vec!["b"]);
+        let buffer = render_tree(tree, 9, 6, &mut state);
+        let expected = Buffer::with_lines([
+            "  Alfa   ",
+            "---------",
+            "▼ Bravo  ",
+            "    Charl",
+            "  ▶ Delta",
+            "    Golf ",
+        ]);
+        assert_eq!(buffer, expected);
+
+        state.select(vec!["h"]);
+        let tree = Tree::new(&items).unwrap().top_level_separator('-');
+        let buffer = render_tree(tree, 9, 6, &mut state);
+        let expected = Buffer::with_lines([
+            "    Charl",
+            "  ▶ Delta",
+            "    Golf ",
+            "---------",
+            "  Hotel  ",
+            "         ",
+        ]);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn marked_style() {
+        let items = TreeItem::example();
+        let marked = Style::new().fg(Color::Yellow);
+        let highlight = Style::new().bg(Color::Blue);
+        let tree = Tree::new(&items)
+            .unwrap()
+            .marked_style(marked)
+            .highlight_style(highlight);
+        let mut state = TreeState::default();
+        state.select(vec!["b"]);
+        state.mark(vec!["b"]);
+        state.mark(vec!["h"]);
+        let buffer = render_tree(tree, 7, 3, &mut state);
+        let mut expected = Buffer::with_lines(["  Alfa ", "▶ Bravo", "  Hotel"]);
+        expected.set_style(Rect::new(0, 1, 7, 1), highlight.patch(marked));
+        expected.set_style(Rect::new(0, 2, 7, 1), marked);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn unfocused_highlight_style() {
+        let items = TreeItem::example();
+        let highlight = Style::new().bg(Color::Blue);
+        let tree = Tree::new(&items)
+            .unwrap()
+            .highlight_style(highlight)
+            .focused(false);
+        let mut state = TreeState::default();
+        state.select(vec!["b"]);
+        let buffer = render_tree(tree, 7, 3, &mut state);
+        let mut expected = Buffer::with_lines(["  Alfa ", "▶ Bravo", "  Hotel"]);
+        expected.set_style(
+            Rect::new(0, 1, 7, 1),
+            Style::new().add_modifier(Modifier::REVERSED),
+        );
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn multiline_item_is_indented_on_every_line() {
+        let items =
+            [TreeItem::new("a", "Alfa", vec![TreeItem::new_leaf("b", "Bravo\nBravo2")]).unwrap()];
+        let tree = Tree::new(&items).unwrap().highlight_symbol(">>");
+        let mut state = TreeState::default();
+        state.open(vec!["a"]);
+        state.select(vec!["a", "b"]);
+        let buffer = render_tree(tree, 12, 4, &mut state);
+        let expected = Buffer::with_lines([
+            "  ▼ Alfa    ",
+            ">>    Bravo ",
+            ">>    Bravo2",
+            "            ",
+        ]);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn highlight_symbol_reserved_width() {
+        let items = TreeItem::example();
+        let tree = Tree::new(&items)
+            .unwrap()
+            .highlight_symbol(">")
+            .highlight_symbol_reserved_width(3);
+        let mut state = TreeState::default();
+        state.select(vec!["b"]);
+        let buffer = render_tree(tree, 10, 3, &mut state);
+        let expected = Buffer::with_lines(["     Alfa ", ">  ▶ Bravo", "     Hotel"]);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn max_depth() {
+        let items = TreeItem::example();
+        let tree = Tree::new(&items).unwrap().max_depth(2);
+        let mut state = TreeState::default();
+        state.open(vec!["b"]);
+        state.open(vec!["b", "d"]);
+        let buffer = render_tree(tree, 13, 6, &mut state);
+        let expected = Buffer::with_lines([
+            "  Alfa       ",
+            "▼ Bravo      ",
+            "    Charlie  ",
+            "  … Delta    ",
+            "    Golf     ",
+            "  Hotel      ",
+        ]);
+        assert_eq!(buffer, expected);
+        assert_eq!(state.visible_node_count(), 8);
+        assert_eq!(state.visible_range(), 0..8);
+
+        // Hidden nodes are skipped by the navigation
+        state.select(vec!["b", "d"]);
+        assert!(state.key_down());
+        assert_eq!(state.selected(), ["b", "g"]);
+        assert!(state.key_up());
+        assert_eq!(state.selected(), ["b", "d"]);
+    }
+
+    #[test]
+    fn max_depth_line_numbers() {
+        let items = TreeItem::example();
+        let tree = Tree::new(&items)
+            .unwrap()
+            .max_depth(2)
+            .line_numbers(true)
+            .line_number_style(Style::new());
+        let mut state = TreeState::default();
+        state.open(vec!["b"]);
+        state.open(vec!["b", "d"]);
+        let buffer = render_tree(tree, 15, 6, &mut state);
+        let expected = Buffer::with_lines([
+            "1   Alfa       ",
+            "2 ▼ Bravo      ",
+            "3     Charlie  ",
+            "4   … Delta    ",
+            "5     Golf     ",
+            "6   Hotel      ",
+        ]);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn item_with_row_style() {
+        let red = Style::new().fg(Color::Red);
+        let highlight = Style::new().fg(Color::Blue);
+        let items = [
+            TreeItem::new_leaf("a", "Alfa").with_row_style(red),
+            TreeItem::new_leaf("b", "Bravo").with_row_style(red),
+            TreeItem::new_leaf("c", "Charlie"),
+        ];
+        assert_eq!(items[0].row_style(), red);
+        let tree = Tree::new(&items).unwrap().highlight_style(highlight);
+        let mut state = TreeState::default();
+        state.select(vec!["b"]);
+        let buffer = render_tree(tree, 9, 3, &mut state);
+        let mut expected = Buffer::with_lines(["  Alfa   ", "  Bravo  ", "  Charlie"]);
+        expected.set_style(Rect::new(0, 0, 9, 1), red);
+        expected.set_style(Rect::new(0, 1, 9, 1), highlight);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn gap_between_top_level_items() {
+        let items = TreeItem::example();
+        let tree = Tree::new(&items)
+            .unwrap()
+            .gap_between_top_level_items(1)
+            .top_level_separator('-');
+        let mut state = TreeState::default();
+        state.open(vec!["b"]);
+        let buffer = render_tree(tree, 9, 8, &mut state);
+        let expected = Buffer::with_lines([
+            "  Alfa   ",
+            "         ",
+            "---------",
+            "▼ Bravo  ",
+            "    Charl",
+            "  ▶ Delta",
+            "    Golf ",
+            "         ",
+        ]);
+        assert_eq!(buffer, expected);
+        assert_eq!(state.total_required_height(), 10);
+
+        let position = |y| ratatui::layout::Position::new(3, y);
+        assert_eq!(state.rendered_at(position(0)), Some(&["a"][..]));
+        assert!(state.rendered_at(position(1)).is_none());
+        assert!(state.rendered_at(position(2)).is_none());
+        assert_eq!(state.rendered_at(position(3)), Some(&["b"][..]));
+        assert!(!state.click_at(position(2)));
+        assert!(state.selected().is_empty());
+    }
+
+    #[test]
+    fn node_symbols_per_depth() {
+        let items = TreeItem::example();
+        let tree = Tree::new(&items)
+            .unwrap()
+            .node_closed_symbols(vec!["> ", "+ "])
+            .node_open_symbols(vec!["v "]);
+        let mut state = TreeState::default();
+        state.open(vec!["b"]);
+        let buffer = render_tree(tree, 9, 4, &mut state);
+        let expected = Buffer::with_lines(["  Alfa   ", "v Bravo  ", "    Charl", "  + Delta"]);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn render_to_string_works() {
+        let items = TreeItem::example();
+        let mut state = TreeState::default();
+        state.open(vec!["b"]);
+        let text = Tree::new(&items)
+            .unwrap()
+            .render_to_string(12, 6, &mut state);
+        assert_eq!(
+            text,
+            "  Alfa\n▼ Bravo\n    Charlie\n  ▶ Delta\n    Golf\n  Hotel\n"
+        );
+    }
+
+    #[test]
+    fn scrollbar_symbols() {
+        let items = TreeItem::example();
+        let tree = Tree::new(&items)
+            .unwrap()
+            .scrollbar_begin_symbol(None)
+            .scrollbar_end_symbol(None)
+            .scrollbar_track_symbol(Some("|"))
+            .scrollbar_thumb_symbol("#");
+        let mut state = TreeState::default();
+        state.open(vec!["b"]);
+        state.open(vec!["b", "d"]);
+        let buffer = render_tree(tree, 12, 3, &mut state);
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "  Alfa     #",
+            "▼ Bravo    |",
+            "    Charlie|",
+        ]);
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn empty_message() {
+        let items: [TreeItem<&str>; 0] = [];
+        let tree = Tree::new(&items).unwrap().empty_message("empty");
+        let buffer = render_tree(tree, 9, 3, &mut TreeState::default());
+        let expected = Buffer::with_lines(["         ", "  empty  ", "         "]);
+        assert_eq!(buffer, expected);
+    }
 }
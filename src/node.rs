@@ -0,0 +1,17 @@
+/// Metadata of a node viewable (including by scrolling) on the last render.
+///
+/// Get it via [`TreeState::node_for`](crate::TreeState::node_for) after rendering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Node {
+    /// Zero based depth. Depth 0 means top level with 0 indentation.
+    pub depth: usize,
+    /// Whether the node has children, regardless of being open.
+    pub has_children: bool,
+    /// Whether the node is a separator which can not be selected.
+    pub is_separator: bool,
+    /// Whether the node is not drawn due to the [`Tree::max_depth`](crate::Tree::max_depth) and can not be selected.
+    pub is_hidden: bool,
+    /// Amount of lines of the text.
+    pub height: usize,
+}
@@ -1,6 +1,11 @@
+use std::cmp::Ordering;
 use std::collections::HashSet;
 
-use ratatui::text::Text;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span, Text};
+
+use crate::error::TreeItemError;
+use crate::flatten::{flatten_all, Flattened};
 
 /// One item inside a [`Tree`](crate::Tree).
 ///
@@ -31,13 +36,15 @@ use ratatui::text::Text;
 /// # use tui_tree_widget::TreeItem;
 /// let a = TreeItem::new_leaf("l", "Leaf");
 /// let b = TreeItem::new("r", "Root", vec![a])?;
-/// # Ok::<(), std::io::Error>(())
+/// # Ok::<(), tui_tree_widget::TreeItemError>(())
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Default, Clone)]
 pub struct TreeItem<'text, Identifier> {
     pub(super) identifier: Identifier,
     pub(super) text: Text<'text>,
     pub(super) children: Vec<Self>,
+    pub(super) separator: bool,
+    pub(super) row_style: Style,
 }
 
 impl<'text, Identifier> TreeItem<'text, Identifier>
@@ -54,6 +61,31 @@ where
             identifier,
             text: text.into(),
             children: Vec::new(),
+            separator: false,
+            row_style: Style::new(),
+        }
+    }
+
+    /// Create a new `TreeItem` without children with a styled text.
+    ///
+    /// Shorthand for `TreeItem::new_leaf(identifier, Span::styled(text, style))`.
+    #[must_use]
+    pub fn new_leaf_styled(identifier: Identifier, text: &'text str, style: Style) -> Self {
+        Self::new_leaf(identifier, Span::styled(text, style))
+    }
+
+    /// Create a new `TreeItem` which is rendered as a horizontal separator line.
+    ///
+    /// Separators have no children and are skipped by the navigation and selection methods of the [`TreeState`](crate::TreeState) like [`key_down`](crate::TreeState::key_down) or [`click_at`](crate::TreeState::click_at).
+    /// Their style is configured with [`Tree::separator_style`](crate::Tree::separator_style).
+    #[must_use]
+    pub fn new_separator(identifier: Identifier) -> Self {
+        Self {
+            identifier,
+            text: Text::from(Line::default()),
+            children: Vec::new(),
+            separator: true,
+            row_style: Style::new(),
         }
     }
 
@@ -62,7 +94,11 @@ where
     /// # Errors
     ///
     /// Errors when there are duplicate identifiers in the children.
-    pub fn new<T>(identifier: Identifier, text: T, children: Vec<Self>) -> std::io::Result<Self>
+    pub fn new<T>(
+        identifier: Identifier,
+        text: T,
+        children: Vec<Self>,
+    ) -> Result<Self, TreeItemError>
     where
         T: Into<Text<'text>>,
     {
@@ -71,25 +107,165 @@ where
             .map(|item| &item.identifier)
             .collect::<HashSet<_>>();
         if identifiers.len() != children.len() {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::AlreadyExists,
-                "The children contain duplicate identifiers",
-            ));
+            return Err(TreeItemError::DuplicateIdentifiers);
         }
 
         Ok(Self {
             identifier,
             text: text.into(),
             children,
+            separator: false,
+            row_style: Style::new(),
         })
     }
 
+    /// Create a new `TreeItem` with children while renaming children with duplicate identifiers instead of erroring like [`new`](Self::new).
+    ///
+    /// The identifier is generic so `rename` creates the new identifier from the duplicate one and a counter starting at 2.
+    /// It is called with increasing counters until an unused identifier is returned.
+    ///
+    /// Returns the new item and the new identifiers of the renamed children.
+    ///
+    /// ```
+    /// # use tui_tree_widget::TreeItem;
+    /// let children = vec![
+    ///     TreeItem::new_leaf("a".to_owned(), "Alfa"),
+    ///     TreeItem::new_leaf("a".to_owned(), "Alfa"),
+    /// ];
+    /// let (item, renamed) = TreeItem::new_deduplicating(
+    ///     "root".to_owned(),
+    ///     "Root",
+    ///     children,
+    ///     |identifier, counter| format!("{identifier}_{counter}"),
+    /// );
+    /// assert_eq!(renamed, ["a_2"]);
+    /// ```
+    pub fn new_deduplicating<T, F>(
+        identifier: Identifier,
+        text: T,
+        mut children: Vec<Self>,
+        mut rename: F,
+    ) -> (Self, Vec<Identifier>)
+    where
+        T: Into<Text<'text>>,
+        F: FnMut(&Identifier, usize) -> Identifier,
+    {
+        // All original identifiers are taken up front so a new identifier never clashes with a later child
+        let mut taken = children
+            .iter()
+            .map(|child| child.identifier.clone())
+            .collect::<HashSet<_>>();
+        let mut seen = HashSet::new();
+        let mut renamed = Vec::new();
+        for child in &mut children {
+            if !seen.insert(child.identifier.clone()) {
+                let mut counter = 2;
+                let mut candidate = rename(&child.identifier, counter);
+                while taken.contains(&candidate) {
+                    counter += 1;
+                    candidate = rename(&child.identifier, counter);
+                }
+                taken.insert(candidate.clone());
+                child.identifier = candidate;
+                renamed.push(child.identifier.clone());
+            }
+        }
+
+        let item = Self {
+            identifier,
+            text: text.into(),
+            children,
+            separator: false,
+            row_style: Style::new(),
+        };
+        (item, renamed)
+    }
+
+    /// Set the identifier.
+    ///
+    /// Builder counterpart of [`new_leaf`](Self::new_leaf) and [`new`](Self::new) which allows method chaining:
+    ///
+    /// ```
+    /// # use tui_tree_widget::TreeItem;
+    /// let leaf = TreeItem::default().with_identifier("l").with_text("Leaf");
+    /// let root = TreeItem::default()
+    ///     .with_identifier("r")
+    ///     .with_text("Root")
+    ///     .with_child(leaf)?;
+    /// # Ok::<(), tui_tree_widget::TreeItemError>(())
+    /// ```
+    #[must_use]
+    pub fn with_identifier(mut self, identifier: Identifier) -> Self {
+        self.identifier = identifier;
+        self
+    }
+
+    /// Set the text.
+    #[must_use]
+    pub fn with_text<T>(mut self, text: T) -> Self
+    where
+        T: Into<Text<'text>>,
+    {
+        self.text = text.into();
+        self
+    }
+
+    /// Replace the text in place, for example when the underlying data changed.
+    ///
+    /// Unlike [`with_text`](Self::with_text) this does not require ownership which is helpful for nested children.
+    pub fn update_text<T>(&mut self, text: T)
+    where
+        T: Into<Text<'text>>,
+    {
+        self.text = text.into();
+    }
+
+    /// Set the base style of the text.
+    ///
+    /// Styles of the individual lines and spans of the text take precedence.
+    pub const fn set_style(&mut self, style: Style) {
+        self.text.style = style;
+    }
+
+    /// Set the style of the whole rendered row, for example to color-code a status.
+    ///
+    /// Applied after the text is rendered. The [`Tree::highlight_style`](crate::Tree::highlight_style) of the selected row takes precedence.
+    /// Use [`set_style`](Self::set_style) to only style the text.
+    #[must_use]
+    pub const fn with_row_style(mut self, style: Style) -> Self {
+        self.row_style = style;
+        self
+    }
+
+    /// Add a child to the `TreeItem` while allowing method chaining.
+    /// See also [`add_child`](Self::add_child).
+    ///
+    /// # Errors
+    ///
+    /// Errors when the `identifier` of the `child` already exists in the children.
+    pub fn with_child(mut self, child: Self) -> Result<Self, TreeItemError> {
+        self.add_child(child)?;
+        Ok(self)
+    }
+
+    /// Get the style of the whole rendered row. See [`with_row_style`](Self::with_row_style).
+    #[must_use]
+    pub const fn row_style(&self) -> Style {
+        self.row_style
+    }
+
     /// Get a reference to the identifier.
     #[must_use]
     pub const fn identifier(&self) -> &Identifier {
         &self.identifier
     }
 
+    /// Is this item a separator created with [`new_separator`](Self::new_separator)?
+    #[must_use]
+    pub const fn is_separator(&self) -> bool {
+        self.separator
+    }
+
     #[must_use]
     pub fn children(&self) -> &[Self] {
         &self.children
@@ -109,6 +285,114 @@ where
         self.children.get_mut(index)
     }
 
+    /// Amount of items in this subtree including itself and all nested children.
+    #[must_use]
+    pub fn child_count_recursive(&self) -> usize {
+        self.iter_all().count()
+    }
+
+    /// Iterate over this item and all nested children in depth-first pre-order.
+    ///
+    /// Yields each item together with its depth relative to this item which has the depth 0.
+    pub fn iter_all(&self) -> TreeItemIter<'_, 'text, Identifier> {
+        TreeItemIter {
+            stack: vec![(self, 0)],
+        }
+    }
+
+    /// Get a flat list of all `items` and their nested children regardless of what is open.
+    ///
+    /// Helpful to build a search index.
+    /// Use [`TreeState::flatten`](crate::TreeState::flatten) for what is currently visible.
+    #[must_use]
+    pub fn flatten_all(items: &'text [Self]) -> Vec<Flattened<'text, Identifier>> {
+        flatten_all(items)
+    }
+
+    /// Get all `items` and their nested children regardless of what is open together with their depth and full identifier.
+    ///
+    /// Unlike [`flatten_all`](Self::flatten_all) the identifiers are borrowed instead of cloned.
+    #[must_use]
+    pub fn flatten_with_depth(items: &[Self]) -> Vec<(usize, Vec<&Identifier>, &Self)> {
+        let mut result = Vec::new();
+        // Explicit stack instead of recursion to not overflow on deeply nested items
+        let mut stack = vec![(Vec::new(), items.iter())];
+        while let Some((parent, children)) = stack.last_mut() {
+            let Some(item) = children.next() else {
+                stack.pop();
+                continue;
+            };
+
+            let mut identifier = parent.clone();
+            identifier.push(&item.identifier);
+            stack.push((identifier.clone(), item.children.iter()));
+            result.push((identifier.len() - 1, identifier, item));
+        }
+        result
+    }
+
+    /// Find the full identifier path of the first item with the `target` identifier among the `items` and their nested children.
+    ///
+    /// Searches depth-first regardless of what is open.
+    /// The result can be passed to [`TreeState::move_to_identifier`](crate::TreeState::move_to_identifier) to reveal and select it.
+    #[must_use]
+    pub fn find_path(items: &[Self], target: &Identifier) -> Option<Vec<Identifier>> {
+        Self::flatten_with_depth(items)
+            .into_iter()
+            .find(|(_, _, item)| item.identifier == *target)
+            .map(|(_, identifier, _)| identifier.into_iter().cloned().collect())
+    }
+
+    /// Get the nested child at the `path` relative to this item.
+    ///
+    /// The `path` does not include the identifier of this item.
+    /// An empty `path` returns this item.
+    #[must_use]
+    pub fn find_in_children(&self, path: &[Identifier]) -> Option<&Self> {
+        if path.is_empty() {
+            Some(self)
+        } else {
+            Self::find(&self.children, path)
+        }
+    }
+
+    /// Get the item at the full `identifier` path among the `items` and their nested children.
+    pub(crate) fn find<'a>(items: &'a [Self], identifier: &[Identifier]) -> Option<&'a Self> {
+        let (first, rest) = identifier.split_first()?;
+        let mut item = items.iter().find(|item| item.identifier == *first)?;
+        for part in rest {
+            item = item.children.iter().find(|item| item.identifier == *part)?;
+        }
+        Some(item)
+    }
+
+    /// Sort the children with a comparator function like [`slice::sort_by`].
+    ///
+    /// When `recursive` is `true` the children of the children are sorted too.
+    pub fn sort_children_by<F>(&mut self, mut compare: F, recursive: bool)
+    where
+        F: FnMut(&Self, &Self) -> Ordering,
+    {
+        let mut stack = vec![self];
+        while let Some(item) = stack.pop() {
+            item.children.sort_by(&mut compare);
+            if recursive {
+                stack.extend(&mut item.children);
+            }
+        }
+    }
+
+    /// Sort the children with a key extraction function like [`slice::sort_by_key`].
+    ///
+    /// When `recursive` is `true` the children of the children are sorted too.
+    pub fn sort_children_by_key<F, K>(&mut self, mut key: F, recursive: bool)
+    where
+        F: FnMut(&Self) -> K,
+        K: Ord,
+    {
+        self.sort_children_by(|a, b| key(a).cmp(&key(b)), recursive);
+    }
+
     #[must_use]
     pub fn height(&self) -> usize {
         self.text.height()
@@ -119,22 +403,62 @@ where
     /// # Errors
     ///
     /// Errors when the `identifier` of the `child` already exists in the children.
-    pub fn add_child(&mut self, child: Self) -> std::io::Result<()> {
+    pub fn add_child(&mut self, child: Self) -> Result<(), TreeItemError> {
+        self.insert_child_at(self.children.len(), child)
+    }
+
+    /// Insert a child at the given `index` of the children, shifting all children after it.
+    ///
+    /// When the `index` is bigger than the amount of children, the child is appended like with [`add_child`](Self::add_child).
+    ///
+    /// # Errors
+    ///
+    /// Errors when the `identifier` of the `child` already exists in the children.
+    pub fn insert_child_at(&mut self, index: usize, child: Self) -> Result<(), TreeItemError> {
         let existing = self
             .children
             .iter()
             .map(|item| &item.identifier)
             .collect::<HashSet<_>>();
         if existing.contains(&child.identifier) {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::AlreadyExists,
-                "identifier already exists in the children",
-            ));
+            return Err(TreeItemError::IdentifierAlreadyExists);
         }
 
-        self.children.push(child);
+        let index = index.min(self.children.len());
+        self.children.insert(index, child);
         Ok(())
     }
+
+    /// Remove the child with the given `identifier` from the `TreeItem`.
+    ///
+    /// Returns the removed child or `None` when there is no child with the `identifier`.
+    pub fn remove_child(&mut self, identifier: &Identifier) -> Option<Self> {
+        let index = self
+            .children
+            .iter()
+            .position(|child| child.identifier == *identifier)?;
+        Some(self.children.remove(index))
+    }
+}
+
+/// Iterator over a [`TreeItem`] and all its nested children.
+///
+/// Generated via [`TreeItem::iter_all`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone)]
+pub struct TreeItemIter<'a, 'text, Identifier> {
+    stack: Vec<(&'a TreeItem<'text, Identifier>, usize)>,
+}
+
+impl<'a, 'text, Identifier> Iterator for TreeItemIter<'a, 'text, Identifier> {
+    type Item = (&'a TreeItem<'text, Identifier>, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (item, depth) = self.stack.pop()?;
+        self.stack
+            .extend(item.children.iter().rev().map(|child| (child, depth + 1)));
+        Some((item, depth))
+    }
 }
 
 impl TreeItem<'static, &'static str> {
@@ -164,7 +488,7 @@ impl TreeItem<'static, &'static str> {
 }
 
 #[test]
-#[should_panic = "duplicate identifiers"]
+#[should_panic = "DuplicateIdentifiers"]
 fn tree_item_new_errors_with_duplicate_identifiers() {
     let item = TreeItem::new_leaf("same", "text");
     let another = item.clone();
@@ -172,7 +496,207 @@ fn tree_item_new_errors_with_duplicate_identifiers() {
 }
 
 #[test]
-#[should_panic = "identifier already exists"]
+fn tree_item_builder_works() {
+    let root = TreeItem::default()
+        .with_identifier("root")
+        .with_text("Root")
+        .with_child(TreeItem::new_leaf("a", "Alfa"))
+        .unwrap()
+        .with_child(TreeItem::new_leaf("b", "Bravo"))
+        .unwrap();
+    assert_eq!(root.identifier(), &"root");
+    assert_eq!(root.height(), 1);
+    let children = root
+        .children()
+        .iter()
+        .map(TreeItem::identifier)
+        .collect::<Vec<_>>();
+    assert_eq!(children, [&"a", &"b"]);
+}
+
+#[test]
+fn child_count_recursive_works() {
+    let items = TreeItem::example();
+    let counts = items
+        .iter()
+        .map(TreeItem::child_count_recursive)
+        .collect::<Vec<_>>();
+    assert_eq!(counts, [1, 6, 1]);
+}
+
+#[test]
+fn iter_all_works() {
+    let items = TreeItem::example();
+    let all = items[1]
+        .iter_all()
+        .map(|(item, depth)| (item.identifier, depth))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        all,
+        [("b", 0), ("c", 1), ("d", 1), ("e", 2), ("f", 2), ("g", 1)]
+    );
+}
+
+#[test]
+fn flatten_with_depth_works() {
+    let items = TreeItem::example();
+    let all = TreeItem::flatten_with_depth(&items);
+    assert_eq!(all.len(), 8);
+    let (depth, identifier, item) = &all[5];
+    assert_eq!(*depth, 2);
+    assert_eq!(identifier, &[&"b", &"d", &"f"]);
+    assert_eq!(item.identifier(), &"f");
+}
+
+#[test]
+fn find_path_works() {
+    let items = TreeItem::example();
+    assert_eq!(TreeItem::find_path(&items, &"h"), Some(vec!["h"]));
+    assert_eq!(TreeItem::find_path(&items, &"f"), Some(vec!["b", "d", "f"]));
+    assert_eq!(TreeItem::find_path(&items, &"z"), None);
+}
+
+#[test]
+fn find_in_children_works() {
+    let items = TreeItem::example();
+    let item = &items[1];
+    assert_eq!(item.find_in_children(&[]).unwrap().identifier, "b");
+    assert_eq!(item.find_in_children(&["d", "e"]).unwrap().identifier, "e");
+    assert!(item.find_in_children(&["e"]).is_none());
+}
+
+#[test]
+fn sort_children_works() {
+    fn identifiers<'a>(item: &'a TreeItem<&'static str>) -> Vec<&'a str> {
+        item.children()
+            .iter()
+            .map(|child| child.identifier)
+            .collect()
+    }
+
+    let mut item = TreeItem::example().swap_remove(1);
+    item.sort_children_by_key(|child| core::cmp::Reverse(child.identifier), false);
+    assert_eq!(identifiers(&item), ["g", "d", "c"]);
+    assert_eq!(identifiers(&item.children()[1]), ["e", "f"]);
+
+    item.sort_children_by(|a, b| b.identifier.cmp(a.identifier), true);
+    assert_eq!(identifiers(&item.children()[1]), ["f", "e"]);
+
+    item.sort_children_by_key(|child| child.identifier, true);
+    assert_eq!(identifiers(&item), ["c", "d", "g"]);
+    assert_eq!(identifiers(&item.children()[1]), ["e", "f"]);
+}
+
+#[test]
+fn insert_child_at_works() {
+    let mut item = TreeItem::example().swap_remove(1);
+    item.insert_child_at(0, TreeItem::new_leaf("x", "X"))
+        .unwrap();
+    item.insert_child_at(2, TreeItem::new_leaf("y", "Y"))
+        .unwrap();
+    item.insert_child_at(42, TreeItem::new_leaf("z", "Z"))
+        .unwrap();
+    let identifiers = item
+        .children()
+        .iter()
+        .map(TreeItem::identifier)
+        .collect::<Vec<_>>();
+    assert_eq!(identifiers, [&"x", &"c", &"y", &"d", &"g", &"z"]);
+}
+
+#[test]
+#[should_panic = "IdentifierAlreadyExists"]
+fn tree_item_insert_child_at_errors_with_duplicate_identifiers() {
+    let mut item = TreeItem::example().swap_remove(1);
+    item.insert_child_at(0, TreeItem::new_leaf("g", "Golf"))
+        .unwrap();
+}
+
+#[test]
+fn update_text_and_set_style_works() {
+    use ratatui::style::Color;
+
+    let mut item = TreeItem::new_leaf("a", "Alfa");
+    item.update_text("Alpha\nAlfa");
+    item.set_style(Style::new().fg(Color::Red));
+    assert_eq!(item.height(), 2);
+    assert_eq!(item.text.style, Style::new().fg(Color::Red));
+}
+
+#[test]
+fn new_leaf_styled_works() {
+    use ratatui::style::Color;
+
+    let style = Style::new().fg(Color::Green);
+    let item = TreeItem::new_leaf_styled("a", "Alfa", style);
+    assert_eq!(item.text, Text::from(Span::styled("Alfa", style)));
+}
+
+#[test]
+fn new_deduplicating_works() {
+    let children = vec![
+        TreeItem::new_leaf(1, "One"),
+        TreeItem::new_leaf(12, "Twelve"),
+        TreeItem::new_leaf(1, "One"),
+        TreeItem::new_leaf(1, "One"),
+    ];
+    let (item, renamed) =
+        TreeItem::new_deduplicating(0, "Root", children, |identifier, counter| {
+            identifier * 10 + counter
+        });
+    assert_eq!(renamed, [13, 14]);
+    let identifiers = item
+        .children()
+        .iter()
+        .map(TreeItem::identifier)
+        .collect::<Vec<_>>();
+    assert_eq!(identifiers, [&1, &12, &13, &14]);
+}
+
+#[test]
+fn new_deduplicating_keeps_later_original_identifiers() {
+    let children = vec![
+        TreeItem::new_leaf(1, "One"),
+        TreeItem::new_leaf(1, "One"),
+        TreeItem::new_leaf(12, "Twelve"),
+    ];
+    let (item, renamed) =
+        TreeItem::new_deduplicating(0, "Root", children, |identifier, counter| {
+            identifier * 10 + counter
+        });
+    assert_eq!(renamed, [13]);
+    let identifiers = item
+        .children()
+        .iter()
+        .map(TreeItem::identifier)
+        .collect::<Vec<_>>();
+    assert_eq!(identifiers, [&1, &13, &12]);
+}
+
+#[test]
+fn remove_child_works() {
+    let mut item = TreeItem::example().swap_remove(1);
+    let removed = item.remove_child(&"d").expect("d is a child");
+    assert_eq!(removed.identifier(), &"d");
+    assert_eq!(removed.children().len(), 2);
+    assert_eq!(item.children().len(), 2);
+    assert!(item.remove_child(&"d").is_none());
+}
+
+#[test]
+#[should_panic = "IdentifierAlreadyExists"]
+fn tree_item_with_child_errors_with_duplicate_identifiers() {
+    let item = TreeItem::new_leaf("same", "text");
+    let another = item.clone();
+    let _ = TreeItem::new_leaf("root", "Root")
+        .with_child(item)
+        .unwrap()
+        .with_child(another)
+        .unwrap();
+}
+
+#[test]
+#[should_panic = "IdentifierAlreadyExists"]
 fn tree_item_add_child_errors_with_duplicate_identifiers() {
     let item = TreeItem::new_leaf("same", "text");
     let another = item.clone();
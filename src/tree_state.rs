@@ -3,6 +3,7 @@ use std::collections::HashSet;
 use ratatui::layout::{Position, Rect};
 
 use crate::flatten::{flatten, Flattened};
+use crate::node::Node;
 use crate::tree_item::TreeItem;
 
 /// Keeps the state of what is currently selected and what was opened in a [`Tree`](crate::Tree).
@@ -18,31 +19,209 @@ use crate::tree_item::TreeItem;
 ///
 /// let mut state = TreeState::<Identifier>::default();
 /// ```
+///
+/// # Serde
+///
+/// With the `serde` feature the opened, selected and marked identifiers can be (de)serialized to persist them.
+/// Everything else like the scroll position is skipped and starts with its default.
 #[must_use]
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "Identifier: serde::Serialize",
+        deserialize = "Identifier: serde::Deserialize<'de> + Eq + core::hash::Hash"
+    ))
+)]
 pub struct TreeState<Identifier> {
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub(super) offset: usize,
+    /// Offset actually rendered while the scroll animation catches up with `offset`
+    #[cfg(feature = "animation")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) animated_offset: usize,
     pub(super) opened: HashSet<Vec<Identifier>>,
     pub(super) selected: Vec<Identifier>,
+    /// Marked nodes independent of the selection
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(super) marked: HashSet<Vec<Identifier>>,
+    /// When the selection was changed the last time
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) last_selection_changed_at: Option<std::time::Instant>,
+    /// Only read the clock on selection changes when enabled
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) track_selection_age: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub(super) ensure_selected_in_view_on_next_render: bool,
 
+    /// Amount of columns the text is scrolled to the right
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) x_offset: usize,
+    /// Wrap around at the beginning and end with `key_up` and `key_down`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) wrap_navigation: bool,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub(super) last_area: Rect,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub(super) last_biggest_index: usize,
     /// All identifiers open on last render
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub(super) last_identifiers: Vec<Vec<Identifier>>,
-    /// Identifier rendered at `y` on last render
-    pub(super) last_rendered_identifiers: Vec<(u16, Vec<Identifier>)>,
+    /// Width of the widest text of all identifiers open on last render
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) last_max_content_width: usize,
+    /// Identifier rendered in the rows on last render
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) last_rendered_identifiers: Vec<(core::ops::Range<u16>, Vec<Identifier>)>,
+    /// Rows needed for all `last_identifiers` including rows between top level nodes
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) last_total_height: usize,
+    /// Indices of the `last_identifiers` drawn in the viewport
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) last_rendered_range: core::ops::Range<usize>,
+    /// Rows needed for each of the `last_identifiers` including rows in front of top level nodes
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) last_heights: Vec<usize>,
+    /// Metadata of each of the `last_identifiers`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) last_nodes: Vec<Node>,
+}
+
+/// Changes between two [`TreeState`]s.
+///
+/// Generated via [`TreeState::diff`].
+#[must_use]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeStateDiff<Identifier> {
+    /// Nodes open now which were closed before. In no particular order.
+    pub newly_opened: Vec<Vec<Identifier>>,
+    /// Nodes closed now which were open before. In no particular order.
+    pub newly_closed: Vec<Vec<Identifier>>,
+    pub selection_changed: bool,
+    /// Selection of the previous state. `None` when nothing was selected.
+    pub previous_selection: Option<Vec<Identifier>>,
+}
+
+/// Plain text view of the visible nodes of a [`TreeState`] for debugging.
+///
+/// Generated via [`TreeState::debug_display`].
+#[must_use]
+pub struct TreeStateDebugView<'a, Identifier> {
+    state: &'a TreeState<Identifier>,
+    items: &'a [TreeItem<'a, Identifier>],
+}
+
+impl<Identifier> core::fmt::Display for TreeStateDebugView<'_, Identifier>
+where
+    Identifier: Clone + PartialEq + Eq + core::hash::Hash,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for flattened in self.state.flatten(self.items) {
+            let symbol = if flattened.is_leaf() {
+                '\u{b7}' // Middle dot
+            } else if self.state.opened.contains(&flattened.identifier) {
+                '\u{25bc}' // Arrow down
+            } else {
+                '\u{25b6}' // Arrow to right
+            };
+            let indent = "  ".repeat(flattened.depth());
+            write!(f, "{indent}{symbol}")?;
+            for line in &flattened.item.text.lines {
+                write!(f, " {line}")?;
+            }
+            if flattened.identifier == self.state.selected {
+                write!(f, " [*]")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
 }
 
 impl<Identifier> TreeState<Identifier>
 where
     Identifier: Clone + PartialEq + Eq + core::hash::Hash,
 {
+    /// Create a new `TreeState` with the given identifier selected.
+    ///
+    /// ```
+    /// # use tui_tree_widget::TreeState;
+    /// let state = TreeState::new_with_selection(vec!["b", "c"]);
+    /// assert_eq!(state.selected(), ["b", "c"]);
+    /// ```
+    pub fn new_with_selection(identifier: Vec<Identifier>) -> Self {
+        Self {
+            selected: identifier,
+            ensure_selected_in_view_on_next_render: true,
+            ..Self::empty()
+        }
+    }
+
+    /// Create a new `TreeState` with the given nodes opened.
+    ///
+    /// ```
+    /// # use tui_tree_widget::TreeState;
+    /// let state = TreeState::new_with_opened([vec!["b"], vec!["b", "d"]]);
+    /// assert!(state.is_open(&["b", "d"]));
+    /// ```
+    pub fn new_with_opened<I>(identifiers: I) -> Self
+    where
+        I: IntoIterator<Item = Vec<Identifier>>,
+    {
+        let mut state = Self::empty();
+        state.extend_opened(identifiers);
+        state
+    }
+
+    /// Like [`Default`] but without requiring `Identifier: Default`.
+    fn empty() -> Self {
+        Self {
+            offset: 0,
+            #[cfg(feature = "animation")]
+            animated_offset: 0,
+            opened: HashSet::new(),
+            selected: Vec::new(),
+            marked: HashSet::new(),
+            last_selection_changed_at: None,
+            track_selection_age: false,
+            ensure_selected_in_view_on_next_render: false,
+            x_offset: 0,
+            wrap_navigation: false,
+            last_area: Rect::default(),
+            last_biggest_index: 0,
+            last_identifiers: Vec::new(),
+            last_max_content_width: 0,
+            last_rendered_identifiers: Vec::new(),
+            last_total_height: 0,
+            last_rendered_range: 0..0,
+            last_heights: Vec::new(),
+            last_nodes: Vec::new(),
+        }
+    }
+
     #[must_use]
     pub const fn get_offset(&self) -> usize {
         self.offset
     }
 
+    /// Amount of columns the text is scrolled horizontally.
+    #[must_use]
+    pub const fn get_x_offset(&self) -> usize {
+        self.x_offset
+    }
+
+    /// Returns `true` while the rendered scroll position has not yet reached the target offset.
+    ///
+    /// Render again to continue the animation.
+    /// See [`Tree::animate_scroll`](crate::Tree::animate_scroll).
+    #[cfg(feature = "animation")]
+    #[must_use]
+    pub const fn is_scroll_animating(&self) -> bool {
+        self.animated_offset != self.offset
+    }
+
     #[must_use]
     #[deprecated = "Use self.opened()"]
     pub fn get_all_opened(&self) -> Vec<Vec<Identifier>> {
@@ -54,11 +233,265 @@ where
         &self.opened
     }
 
+    /// Get a copy of the open nodes to restore them later with [`restore_open_set`](Self::restore_open_set).
+    ///
+    /// Keep a history of snapshots to undo accidentally closed subtrees:
+    ///
+    /// ```
+    /// # use tui_tree_widget::TreeState;
+    /// let mut state = TreeState::default();
+    /// state.open(vec!["b"]);
+    /// state.open(vec!["b", "d"]);
+    ///
+    /// let mut history = vec![state.snapshot_open_set()];
+    /// state.close_all();
+    ///
+    /// if let Some(snapshot) = history.pop() {
+    ///     state.restore_open_set(snapshot);
+    /// }
+    /// assert!(state.is_open(&["b", "d"]));
+    /// ```
+    #[must_use]
+    pub fn snapshot_open_set(&self) -> HashSet<Vec<Identifier>> {
+        self.opened.clone()
+    }
+
+    /// Replace the open nodes with a snapshot taken with [`snapshot_open_set`](Self::snapshot_open_set).
+    pub fn restore_open_set(&mut self, snapshot: HashSet<Vec<Identifier>>) {
+        self.opened = snapshot;
+    }
+
+    /// Is the node with the given identifier open?
+    #[must_use]
+    pub fn is_open(&self, identifier: &[Identifier]) -> bool {
+        self.opened.contains(identifier)
+    }
+
+    /// Are all ancestors of the node with the given identifier open?
+    ///
+    /// Then it is reachable (visible when scrolled to), assuming it exists.
+    /// Top level nodes are always reachable.
+    /// Use [`move_to_identifier`](Self::move_to_identifier) to open the ancestors and select it.
+    #[must_use]
+    pub fn is_reachable(&self, identifier: &[Identifier]) -> bool {
+        !identifier.is_empty()
+            && (1..identifier.len()).all(|length| self.opened.contains(&identifier[..length]))
+    }
+
+    /// Get the opened identifiers sorted lexicographically.
+    ///
+    /// Unlike [`opened`](Self::opened) the order is deterministic which is helpful for displaying or serializing.
+    #[must_use]
+    pub fn opened_sorted(&self) -> Vec<&[Identifier]>
+    where
+        Identifier: Ord,
+    {
+        let mut opened = self.opened.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        opened.sort_unstable();
+        opened
+    }
+
+    /// Get the opened identifiers sorted by their depth first and lexicographically afterwards.
+    #[must_use]
+    pub fn opened_by_depth(&self) -> Vec<&[Identifier]>
+    where
+        Identifier: Ord,
+    {
+        let mut opened = self.opened.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        opened.sort_unstable_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        opened
+    }
+
     #[must_use]
     pub fn selected(&self) -> &[Identifier] {
         &self.selected
     }
 
+    /// Format the current selection as a breadcrumb like `"b / d / e"` for a status bar.
+    ///
+    /// Each part of the identifier is shown via `display_fn`.
+    /// See [`selection_path_display_default`](Self::selection_path_display_default) for identifiers implementing [`Display`](core::fmt::Display).
+    #[must_use]
+    pub fn selection_path_display<F>(&self, display_fn: F) -> String
+    where
+        F: Fn(&Identifier) -> &str,
+    {
+        self.selected
+            .iter()
+            .map(display_fn)
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+
+    /// Format the current selection as a breadcrumb like `"b / d / e"` for a status bar.
+    ///
+    /// ```
+    /// # use tui_tree_widget::TreeState;
+    /// let mut state = TreeState::default();
+    /// state.select(vec!["b", "d", "e"]);
+    /// assert_eq!(state.selection_path_display_default(), "b / d / e");
+    /// ```
+    #[must_use]
+    pub fn selection_path_display_default(&self) -> String
+    where
+        Identifier: core::fmt::Display,
+    {
+        self.selected
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+
+    /// Is the node with the given identifier the current selection?
+    #[must_use]
+    pub fn is_selected(&self, identifier: &[Identifier]) -> bool {
+        self.selected == identifier
+    }
+
+    /// Time since the selection was changed the last time.
+    ///
+    /// Helpful to debounce expensive work depending on the selection like loading a preview.
+    /// Needs to be enabled with [`set_track_selection_age`](Self::set_track_selection_age).
+    /// Returns `None` when the selection was not changed since then.
+    #[must_use]
+    pub fn selection_age(&self) -> Option<std::time::Duration> {
+        self.last_selection_changed_at
+            .map(|changed_at| changed_at.elapsed())
+    }
+
+    /// Nodes marked independent of the selection, for example to act on multiple files at once.
+    #[must_use]
+    pub const fn marked(&self) -> &HashSet<Vec<Identifier>> {
+        &self.marked
+    }
+
+    /// Area the nodes were rendered in on the last render, inside a possible block.
+    ///
+    /// Helpful for custom mouse handling, for example to check whether a click is inside the widget.
+    #[must_use]
+    pub const fn last_rendered_area(&self) -> Rect {
+        self.last_area
+    }
+
+    /// Amount of nodes which were viewable (including by scrolling) on the last render.
+    #[must_use]
+    pub const fn visible_node_count(&self) -> usize {
+        self.last_identifiers.len()
+    }
+
+    /// Amount of rows needed to render all viewable (including by scrolling) nodes on the last render.
+    ///
+    /// Includes the rows of [`Tree::top_level_separator`](crate::Tree::top_level_separator) and [`Tree::gap_between_top_level_items`](crate::Tree::gap_between_top_level_items).
+    /// This is the same content length the built-in scrollbar uses which helps to build a custom one.
+    #[must_use]
+    pub const fn total_required_height(&self) -> usize {
+        self.last_total_height
+    }
+
+    /// Show the currently viewable (including by scrolling) nodes as plain text for debugging.
+    ///
+    /// Each node is on its own line indented by its depth.
+    /// The selected node is marked with `[*]`.
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![
+    ///     TreeItem::new("a", "Alfa", vec![TreeItem::new_leaf("b", "Bravo")])?,
+    ///     TreeItem::new_leaf("c", "Charlie"),
+    /// ];
+    /// let mut state = TreeState::default();
+    /// state.open(vec!["a"]);
+    /// state.select(vec!["a", "b"]);
+    /// assert_eq!(
+    ///     state.debug_display(&items).to_string(),
+    ///     "▼ Alfa\n  · Bravo [*]\n· Charlie\n"
+    /// );
+    /// # Ok::<(), tui_tree_widget::TreeItemError>(())
+    /// ```
+    pub const fn debug_display<'a>(
+        &'a self,
+        items: &'a [TreeItem<'a, Identifier>],
+    ) -> TreeStateDebugView<'a, Identifier> {
+        TreeStateDebugView { state: self, items }
+    }
+
+    /// Compare with a `previous` state to see what changed.
+    ///
+    /// Helpful to trigger side effects like loading data exactly when a node was opened.
+    ///
+    /// ```
+    /// # use tui_tree_widget::TreeState;
+    /// let previous = TreeState::<&str>::default();
+    /// let mut state = TreeState::default();
+    /// state.open(vec!["a"]);
+    /// let diff = state.diff(&previous);
+    /// assert_eq!(diff.newly_opened, [vec!["a"]]);
+    /// assert!(!diff.selection_changed);
+    /// ```
+    pub fn diff(&self, previous: &Self) -> TreeStateDiff<Identifier> {
+        TreeStateDiff {
+            newly_opened: self.opened.difference(&previous.opened).cloned().collect(),
+            newly_closed: previous.opened.difference(&self.opened).cloned().collect(),
+            selection_changed: self.selected != previous.selected,
+            previous_selection: (!previous.selected.is_empty()).then(|| previous.selected.clone()),
+        }
+    }
+
+    /// Get the identifier at the given index of the nodes viewable (including by scrolling) on the last render.
+    #[must_use]
+    pub fn nth_visible(&self, index: usize) -> Option<&[Identifier]> {
+        self.last_identifiers.get(index).map(Vec::as_slice)
+    }
+
+    /// Get the index of the identifier in the nodes viewable (including by scrolling) on the last render.
+    #[must_use]
+    pub fn visible_position_of(&self, identifier: &[Identifier]) -> Option<usize> {
+        self.last_identifiers
+            .iter()
+            .position(|visible| visible == identifier)
+    }
+
+    /// Get the metadata of the given identifier when it was viewable (including by scrolling) on the last render.
+    ///
+    /// Helpful to check for children or the depth without access to the [`TreeItem`]s.
+    #[must_use]
+    pub fn node_for(&self, identifier: &[Identifier]) -> Option<&Node> {
+        self.visible_position_of(identifier)
+            .and_then(|index| self.last_nodes.get(index))
+    }
+
+    /// Get all siblings of the given identifier which were viewable (including by scrolling) on the last render.
+    ///
+    /// Siblings share the same parent. The given identifier itself is included.
+    /// Top level identifiers are siblings of all other top level identifiers.
+    #[must_use]
+    pub fn siblings_of(&self, identifier: &[Identifier]) -> Vec<&[Identifier]> {
+        let Some((_, parent)) = identifier.split_last() else {
+            return Vec::new();
+        };
+        self.last_identifiers
+            .iter()
+            .filter(|other| other.len() == identifier.len() && other.starts_with(parent))
+            .map(Vec::as_slice)
+            .collect()
+    }
+
+    /// Get all siblings of the current selection including itself.
+    /// See [`siblings_of`](Self::siblings_of).
+    #[must_use]
+    pub fn current_siblings(&self) -> Vec<&[Identifier]> {
+        self.siblings_of(&self.selected)
+    }
+
+    /// Range of the indices of the nodes rendered in the viewport on the last render.
+    ///
+    /// The end is exclusive. Together with [`visible_node_count`](Self::visible_node_count) this can be used for a "N of M" indicator.
+    #[must_use]
+    pub fn visible_range(&self) -> core::ops::Range<usize> {
+        self.last_rendered_range.clone()
+    }
+
     /// Get a flat list of all currently viewable (including by scrolling) [`TreeItem`]s with this `TreeState`.
     #[must_use]
     pub fn flatten<'text>(
@@ -83,9 +516,76 @@ where
         self.ensure_selected_in_view_on_next_render = true;
         let changed = self.selected != identifier;
         self.selected = identifier;
+        if changed {
+            self.selection_changed();
+        }
         changed
     }
 
+    /// Open all ancestors of the given identifier and select it.
+    ///
+    /// Helpful to navigate to a deeply nested node, for example from a search result.
+    ///
+    /// Returns `true` when the selection changed.
+    pub fn move_to_identifier(&mut self, identifier: Vec<Identifier>) -> bool {
+        for ancestor in Self::ancestors_of(&identifier) {
+            self.open(ancestor);
+        }
+        self.select(identifier)
+    }
+
+    /// Get the identifiers of all ancestors of the given identifier, starting with the top level one.
+    ///
+    /// These have to be open for the identifier to be visible.
+    /// Top level identifiers have no ancestors.
+    ///
+    /// ```
+    /// # use tui_tree_widget::TreeState;
+    /// let ancestors = TreeState::ancestors_of(&["a", "b", "c"]);
+    /// assert_eq!(ancestors, [vec!["a"], vec!["a", "b"]]);
+    /// ```
+    #[must_use]
+    pub fn ancestors_of(identifier: &[Identifier]) -> Vec<Vec<Identifier>> {
+        (1..identifier.len())
+            .map(|length| identifier[..length].to_vec())
+            .collect()
+    }
+
+    /// Mark a tree node. See [`Tree::marked_style`](crate::Tree::marked_style).
+    ///
+    /// Returns `true` when it was not marked and has been marked.
+    /// Returns `false` when it was already marked.
+    pub fn mark(&mut self, identifier: Vec<Identifier>) -> bool {
+        if identifier.is_empty() {
+            false
+        } else {
+            self.marked.insert(identifier)
+        }
+    }
+
+    /// Unmark a tree node.
+    ///
+    /// Returns `true` when it was marked and has been unmarked.
+    /// Returns `false` when it was not marked.
+    pub fn unmark(&mut self, identifier: &[Identifier]) -> bool {
+        self.marked.remove(identifier)
+    }
+
+    /// Toggles whether a tree node is marked.
+    /// When it is currently marked, then [`unmark`](Self::unmark) is called. Otherwise [`mark`](Self::mark).
+    ///
+    /// Returns `true` when a node is marked / unmarked.
+    /// As toggling always changes something, this only returns `false` when an empty identifier is given.
+    pub fn toggle_mark(&mut self, identifier: Vec<Identifier>) -> bool {
+        if identifier.is_empty() {
+            false
+        } else if self.marked.contains(&identifier) {
+            self.unmark(&identifier)
+        } else {
+            self.mark(identifier)
+        }
+    }
+
     /// Open a tree node.
     /// Returns `true` when it was closed and has been opened.
     /// Returns `false` when it was already open.
@@ -97,6 +597,25 @@ where
         }
     }
 
+    /// Open multiple tree nodes at once.
+    ///
+    /// Helpful to restore previously saved open nodes.
+    /// Empty identifiers are ignored.
+    ///
+    /// Returns the amount of nodes which were closed and have been opened.
+    pub fn extend_opened<I>(&mut self, identifiers: I) -> usize
+    where
+        I: IntoIterator<Item = Vec<Identifier>>,
+    {
+        let before = self.opened.len();
+        self.opened.extend(
+            identifiers
+                .into_iter()
+                .filter(|identifier| !identifier.is_empty()),
+        );
+        self.opened.len() - before
+    }
+
     /// Close a tree node.
     /// Returns `true` when it was open and has been closed.
     /// Returns `false` when it was already closed.
@@ -140,6 +659,112 @@ where
         self.open(self.selected.clone())
     }
 
+    /// Open all nodes with children up to the given depth.
+    ///
+    /// A `max_depth` of 1 opens all top level nodes, 2 additionally opens their children and so on.
+    /// Nodes without children are not opened.
+    ///
+    /// Returns the amount of nodes that were newly opened.
+    pub fn open_to_depth(&mut self, items: &[TreeItem<'_, Identifier>], max_depth: usize) -> usize {
+        let mut newly_opened = 0;
+        loop {
+            let to_open = flatten(&self.opened, items, &[])
+                .into_iter()
+                .filter(|flattened| flattened.depth() < max_depth)
+                .filter(|flattened| !flattened.item.children.is_empty())
+                .map(|flattened| flattened.identifier)
+                .filter(|identifier| !self.opened.contains(identifier))
+                .collect::<Vec<_>>();
+            if to_open.is_empty() {
+                return newly_opened;
+            }
+            newly_opened += to_open.len();
+            self.opened.extend(to_open);
+        }
+    }
+
+    /// Open the node with the given identifier and all nodes below it.
+    ///
+    /// An empty `root` opens every node.
+    /// Nodes without children are not opened.
+    ///
+    /// Returns the amount of nodes that were newly opened.
+    pub fn open_all_below(
+        &mut self,
+        items: &[TreeItem<'_, Identifier>],
+        root: &[Identifier],
+    ) -> usize {
+        let mut newly_opened = 0;
+        let children = if root.is_empty() {
+            items
+        } else {
+            let Some(item) = TreeItem::find(items, root) else {
+                return 0;
+            };
+            if item.children.is_empty() {
+                return 0;
+            }
+            if self.opened.insert(root.to_vec()) {
+                newly_opened += 1;
+            }
+            &item.children
+        };
+
+        let mut stack = children
+            .iter()
+            .map(|child| (root.to_vec(), child))
+            .collect::<Vec<_>>();
+        while let Some((mut identifier, item)) = stack.pop() {
+            if item.children.is_empty() {
+                continue;
+            }
+            identifier.push(item.identifier.clone());
+            stack.extend(
+                item.children
+                    .iter()
+                    .map(|child| (identifier.clone(), child)),
+            );
+            if self.opened.insert(identifier) {
+                newly_opened += 1;
+            }
+        }
+        newly_opened
+    }
+
+    /// Amount of open nodes below the node with the given identifier including itself.
+    ///
+    /// Helpful to show a badge on a node with how much is expanded below it.
+    #[must_use]
+    pub fn count_opened_in_subtree(&self, root: &[Identifier]) -> usize {
+        self.opened
+            .iter()
+            .filter(|identifier| identifier.starts_with(root))
+            .count()
+    }
+
+    /// Close the node with the given identifier and all nodes below it.
+    /// Other branches are not affected.
+    ///
+    /// Returns the amount of nodes that were closed.
+    pub fn close_all_below(&mut self, root: &[Identifier]) -> usize {
+        let before = self.opened.len();
+        self.opened
+            .retain(|identifier| !identifier.starts_with(root));
+        before - self.opened.len()
+    }
+
+    /// Close every node which is not on the path to the current selection.
+    ///
+    /// Useful for accordion like behavior where only the current branch is expanded.
+    ///
+    /// Returns the amount of nodes that were closed.
+    pub fn close_others(&mut self) -> usize {
+        let before = self.opened.len();
+        self.opened
+            .retain(|identifier| self.selected.starts_with(identifier));
+        before - self.opened.len()
+    }
+
     /// Closes all open nodes.
     ///
     /// Returns `true` when any node was closed.
@@ -152,11 +777,93 @@ where
         }
     }
 
+    /// Close all nodes, clear the selection and marks and reset the view like [`reset_view`](Self::reset_view).
+    ///
+    /// Configuration like [`set_wrap_navigation`](Self::set_wrap_navigation) is kept.
+    pub fn reset(&mut self) {
+        self.opened.clear();
+        self.selected.clear();
+        self.marked.clear();
+        self.reset_view();
+    }
+
+    /// Reset the scroll position and forget everything known from the last render.
+    ///
+    /// The open nodes and the selection are kept.
+    /// Helpful when the layout changed.
+    pub fn reset_view(&mut self) {
+        *self = Self {
+            opened: core::mem::take(&mut self.opened),
+            selected: core::mem::take(&mut self.selected),
+            marked: core::mem::take(&mut self.marked),
+            last_selection_changed_at: self.last_selection_changed_at,
+            track_selection_age: self.track_selection_age,
+            wrap_navigation: self.wrap_navigation,
+            ..Self::empty()
+        };
+    }
+
+    /// Find the first node viewable (including by scrolling) on the last render matching the `predicate`.
+    ///
+    /// Helpful for searching, for example with a separately maintained index of the texts.
+    #[must_use]
+    pub fn find_visible<F>(&self, predicate: F) -> Option<&[Identifier]>
+    where
+        F: Fn(&[Identifier]) -> bool,
+    {
+        self.last_identifiers
+            .iter()
+            .map(Vec::as_slice)
+            .find(|identifier| predicate(identifier))
+    }
+
+    /// Select the first node found with [`find_visible`](Self::find_visible).
+    ///
+    /// Returns `true` when the selection changed.
+    /// Returns `false` when nothing was found.
+    pub fn find_and_select<F>(&mut self, predicate: F) -> bool
+    where
+        F: Fn(&[Identifier]) -> bool,
+    {
+        let found = self
+            .last_identifiers
+            .iter()
+            .enumerate()
+            .find(|(index, identifier)| self.is_selectable_index(*index) && predicate(identifier))
+            .map(|(_, identifier)| identifier.clone());
+        found.is_some_and(|identifier| self.select(identifier))
+    }
+
+    /// Select the next node after the current selection matching the `predicate`.
+    /// Wraps around to the beginning when there is no match after the current selection.
+    ///
+    /// Helpful to jump to the next result of a search.
+    /// Use [`find_and_select`](Self::find_and_select) to select the first match instead.
+    ///
+    /// Returns `true` when the selection changed.
+    /// Returns `false` when nothing else matches.
+    pub fn select_next_where<F>(&mut self, predicate: F) -> bool
+    where
+        F: Fn(&[Identifier]) -> bool,
+    {
+        let start = self.selected_index().map_or(0, |current| current + 1);
+        let found = (start..self.last_identifiers.len())
+            .chain(0..start)
+            .filter(|index| self.is_selectable_index(*index))
+            .map(|index| &self.last_identifiers[index])
+            .find(|identifier| predicate(identifier))
+            .cloned();
+        found.is_some_and(|identifier| self.select(identifier))
+    }
+
     /// Select the first node.
     ///
     /// Returns `true` when the selection changed.
     pub fn select_first(&mut self) -> bool {
-        let identifier = self.last_identifiers.first().cloned().unwrap_or_default();
+        let identifier = self
+            .nearest_selectable_index(0, true)
+            .map(|index| self.last_identifiers[index].clone())
+            .unwrap_or_default();
         self.select(identifier)
     }
 
@@ -164,23 +871,57 @@ where
     ///
     /// Returns `true` when the selection changed.
     pub fn select_last(&mut self) -> bool {
-        let new_identifier = self.last_identifiers.last().cloned().unwrap_or_default();
+        let new_identifier = self
+            .nearest_selectable_index(usize::MAX, false)
+            .map(|index| self.last_identifiers[index].clone())
+            .unwrap_or_default();
         self.select(new_identifier)
     }
 
-    /// Select the node on the given index.
+    /// Select the first node currently visible in the rendered area.
     ///
-    /// Returns `true` when the selection changed.
+    /// Unlike [`select_first`](Self::select_first) this does not scroll.
     ///
-    /// This can be useful for mouse clicks.
-    #[deprecated = "Prefer self.click_at or self.rendered_at as visible index is hard to predict with height != 1"]
-    pub fn select_visible_index(&mut self, new_index: usize) -> bool {
-        let new_index = new_index.min(self.last_biggest_index);
-        let new_identifier = self
-            .last_identifiers
-            .get(new_index)
+    /// Returns `true` when the selection changed.
+    pub fn select_first_visible(&mut self) -> bool {
+        let identifier = self
+            .last_rendered_identifiers
+            .iter()
+            .map(|(_, identifier)| identifier)
+            .find(|identifier| self.is_selectable(identifier))
             .cloned()
             .unwrap_or_default();
+        self.select(identifier)
+    }
+
+    /// Select the last node currently visible in the rendered area.
+    ///
+    /// Unlike [`select_last`](Self::select_last) this does not scroll.
+    ///
+    /// Returns `true` when the selection changed.
+    pub fn select_last_visible(&mut self) -> bool {
+        let identifier = self
+            .last_rendered_identifiers
+            .iter()
+            .rev()
+            .map(|(_, identifier)| identifier)
+            .find(|identifier| self.is_selectable(identifier))
+            .cloned()
+            .unwrap_or_default();
+        self.select(identifier)
+    }
+
+    /// Select the node on the given index.
+    ///
+    /// Returns `true` when the selection changed.
+    ///
+    /// This can be useful for mouse clicks.
+    #[deprecated = "Prefer self.click_at, self.rendered_at or self.select_at_rendered_row as visible index is hard to predict with height != 1"]
+    pub fn select_visible_index(&mut self, new_index: usize) -> bool {
+        let new_identifier = self
+            .nearest_selectable_index(new_index, true)
+            .map(|index| self.last_identifiers[index].clone())
+            .unwrap_or_default();
         self.select(new_identifier)
     }
 
@@ -209,14 +950,7 @@ where
     where
         F: FnOnce(Option<usize>) -> usize,
     {
-        let identifiers = &self.last_identifiers;
-        let current_identifier = &self.selected;
-        let current_index = identifiers
-            .iter()
-            .position(|identifier| identifier == current_identifier);
-        let new_index = change_function(current_index).min(self.last_biggest_index);
-        let new_identifier = identifiers.get(new_index).cloned().unwrap_or_default();
-        self.select(new_identifier)
+        self.select_relative(change_function)
     }
 
     /// Move the current selection with the direction/amount by the given function.
@@ -243,17 +977,178 @@ where
     where
         F: FnOnce(Option<usize>) -> usize,
     {
-        let identifiers = &self.last_identifiers;
-        let current_identifier = &self.selected;
-        let current_index = identifiers
-            .iter()
-            .position(|identifier| identifier == current_identifier);
+        let current_index = self.selected_index();
         let new_index = change_function(current_index).min(self.last_biggest_index);
-        let new_identifier = identifiers.get(new_index).cloned().unwrap_or_default();
+        // Skip separators in the direction of the movement
+        let forward = current_index.is_none_or(|current| new_index >= current);
+        let new_identifier = self
+            .nearest_selectable_index(new_index, forward)
+            .map(|index| self.last_identifiers[index].clone())
+            .unwrap_or_default();
         self.select(new_identifier)
     }
 
+    /// Move the selection down by the height of the last rendered area.
+    ///
+    /// Unlike [`scroll_down`](Self::scroll_down) this changes the selection which scrolls as needed.
+    ///
+    /// Returns `true` when the selection changed.
+    pub fn page_down_selection(&mut self) -> bool {
+        let page = usize::from(self.last_area.height);
+        self.select_relative(|current| current.map_or(0, |current| current.saturating_add(page)))
+    }
+
+    /// Move the selection up by the height of the last rendered area.
+    ///
+    /// Unlike [`scroll_up`](Self::scroll_up) this changes the selection which scrolls as needed.
+    ///
+    /// Returns `true` when the selection changed.
+    pub fn page_up_selection(&mut self) -> bool {
+        let page = usize::from(self.last_area.height);
+        self.select_relative(|current| {
+            current.map_or(usize::MAX, |current| current.saturating_sub(page))
+        })
+    }
+
+    /// Index of the current selection in the identifiers of the last render.
+    fn selected_index(&self) -> Option<usize> {
+        self.visible_position_of(&self.selected)
+    }
+
+    /// Separators and nodes hidden by the [`Tree::max_depth`](crate::Tree::max_depth) can not be selected.
+    /// Indices unknown from the last render are considered selectable.
+    fn is_selectable_index(&self, index: usize) -> bool {
+        !self
+            .last_nodes
+            .get(index)
+            .is_some_and(|node| node.is_separator || node.is_hidden)
+    }
+
+    /// See [`is_selectable_index`](Self::is_selectable_index).
+    fn is_selectable(&self, identifier: &[Identifier]) -> bool {
+        self.visible_position_of(identifier)
+            .is_none_or(|index| self.is_selectable_index(index))
+    }
+
+    /// Index of the selectable node nearest to `index` of the last render.
+    /// Searches in the preferred direction first, then the other one.
+    fn nearest_selectable_index(&self, index: usize, forward: bool) -> Option<usize> {
+        let len = self.last_identifiers.len();
+        let index = index.min(len.checked_sub(1)?);
+        if forward {
+            (index..len)
+                .chain((0..index).rev())
+                .find(|index| self.is_selectable_index(*index))
+        } else {
+            (0..=index)
+                .rev()
+                .chain(index + 1..len)
+                .find(|index| self.is_selectable_index(*index))
+        }
+    }
+
+    /// Select the parent of the current selection.
+    ///
+    /// Unlike [`key_left`](Self::key_left) this does not close the currently selected node first.
+    ///
+    /// Returns `true` when the selection changed.
+    /// Returns `false` when nothing or a top level node is selected.
+    pub fn select_parent(&mut self) -> bool {
+        if self.selected.len() <= 1 {
+            return false;
+        }
+        let parent = self.selected[..self.selected.len() - 1].to_vec();
+        self.select(parent)
+    }
+
+    /// Select the top level ancestor of the current selection.
+    ///
+    /// Unlike [`select_first`](Self::select_first) this stays in the branch of the current selection.
+    ///
+    /// Returns `true` when the selection changed.
+    /// Returns `false` when nothing or a top level node is selected.
+    pub fn select_root(&mut self) -> bool {
+        if self.selected.len() <= 1 {
+            return false;
+        }
+        let root = self.selected[..1].to_vec();
+        self.select(root)
+    }
+
+    /// Select the first child of the current selection.
+    ///
+    /// Unlike [`key_right`](Self::key_right) this does not open the currently selected node.
+    ///
+    /// Returns `true` when the selection changed.
+    /// Returns `false` when the selection has no visible children on the last render.
+    pub fn select_child(&mut self) -> bool {
+        let Some(current) = self.selected_index() else {
+            return false;
+        };
+        let depth = self.selected.len();
+        let child = self
+            .last_identifiers
+            .iter()
+            .enumerate()
+            .skip(current + 1)
+            .take_while(|(_, identifier)| {
+                identifier.len() > depth && identifier.starts_with(&self.selected)
+            })
+            .find(|(index, identifier)| {
+                identifier.len() == depth + 1 && self.is_selectable_index(*index)
+            })
+            .map(|(_, identifier)| identifier.clone());
+        child.is_some_and(|child| self.select(child))
+    }
+
+    /// Select the next sibling of the current selection, skipping the children in between.
+    ///
+    /// Returns `true` when the selection changed.
+    /// Returns `false` when there is no next sibling.
+    pub fn select_next_sibling(&mut self) -> bool {
+        let Some(current) = self.selected_index() else {
+            return false;
+        };
+        let depth = self.selected.len();
+        let sibling = self
+            .last_identifiers
+            .iter()
+            .enumerate()
+            .skip(current + 1)
+            .take_while(|(_, identifier)| identifier.len() >= depth)
+            .find(|(index, identifier)| {
+                identifier.len() == depth && self.is_selectable_index(*index)
+            })
+            .map(|(_, identifier)| identifier.clone());
+        sibling.is_some_and(|sibling| self.select(sibling))
+    }
+
+    /// Select the previous sibling of the current selection, skipping the children in between.
+    ///
+    /// Returns `true` when the selection changed.
+    /// Returns `false` when there is no previous sibling.
+    pub fn select_prev_sibling(&mut self) -> bool {
+        let Some(current) = self.selected_index() else {
+            return false;
+        };
+        let depth = self.selected.len();
+        let sibling = self.last_identifiers[..current]
+            .iter()
+            .enumerate()
+            .rev()
+            .take_while(|(_, identifier)| identifier.len() >= depth)
+            .find(|(index, identifier)| {
+                identifier.len() == depth && self.is_selectable_index(*index)
+            })
+            .map(|(_, identifier)| identifier.clone());
+        sibling.is_some_and(|sibling| self.select(sibling))
+    }
+
     /// Get the identifier that was rendered for the given position on last render.
+    ///
+    /// The `position` is in absolute terminal coordinates like the ones of a mouse event.
+    /// A possible block border is already accounted for as only the area inside the block is considered.
+    /// Rows between top level nodes like the [`Tree::top_level_separator`](crate::Tree::top_level_separator) have no identifier.
     #[must_use]
     pub fn rendered_at(&self, position: Position) -> Option<&[Identifier]> {
         if !self.last_area.contains(position) {
@@ -262,18 +1157,39 @@ where
 
         self.last_rendered_identifiers
             .iter()
-            .rev()
-            .find(|(y, _)| position.y >= *y)
+            .find(|(rows, _)| rows.contains(&position.y))
             .map(|(_, identifier)| identifier.as_ref())
     }
 
+    /// Select what was rendered in the given row of the last rendered area.
+    ///
+    /// The `row` is relative to the top of the area inside a possible block.
+    /// Unlike the deprecated [`select_visible_index`](Self::select_visible_index) this respects items with a height bigger than 1.
+    ///
+    /// Returns `true` when the selection changed.
+    pub fn select_at_rendered_row(&mut self, row: u16) -> bool {
+        let position = Position::new(self.last_area.x, self.last_area.y.saturating_add(row));
+        let identifier = self
+            .rendered_at(position)
+            .filter(|identifier| self.is_selectable(identifier))
+            .map(<[Identifier]>::to_vec);
+        identifier.is_some_and(|identifier| self.select(identifier))
+    }
+
     /// Select what was rendered at the given position on last render.
     /// When it is already selected, toggle it.
     ///
+    /// The `position` is in absolute terminal coordinates like the ones of a mouse event.
+    /// Clicks on a possible block border are ignored.
+    /// Use [`click_at_relative`](Self::click_at_relative) for coordinates relative to the tree.
+    ///
     /// Returns `true` when the state changed.
     /// Returns `false` when there was nothing at the given position.
     pub fn click_at(&mut self, position: Position) -> bool {
-        if let Some(identifier) = self.rendered_at(position) {
+        let identifier = self
+            .rendered_at(position)
+            .filter(|identifier| self.is_selectable(identifier));
+        if let Some(identifier) = identifier {
             if identifier == self.selected {
                 self.toggle_selected()
             } else {
@@ -284,16 +1200,55 @@ where
         }
     }
 
+    /// Same as [`click_at`](Self::click_at) but with the `position` relative to the top left of the last rendered area inside a possible block.
+    ///
+    /// Returns `true` when the state changed.
+    pub fn click_at_relative(&mut self, position: Position) -> bool {
+        let position = Position::new(
+            self.last_area.x.saturating_add(position.x),
+            self.last_area.y.saturating_add(position.y),
+        );
+        self.click_at(position)
+    }
+
     /// Ensure the selected [`TreeItem`] is in view on next render
-    pub fn scroll_selected_into_view(&mut self) {
+    pub const fn scroll_selected_into_view(&mut self) {
         self.ensure_selected_in_view_on_next_render = true;
     }
 
+    /// Scroll so the current selection is vertically centered.
+    ///
+    /// Helpful after a big jump like navigating to a search result.
+    /// Uses the area and nodes of the last render.
+    ///
+    /// Returns `true` when the scroll position changed.
+    /// Returns `false` when nothing is selected or the selection was not visible on the last render.
+    pub fn scroll_center(&mut self) -> bool {
+        let Some(index) = self.selected_index() else {
+            return false;
+        };
+        self.ensure_selected_in_view_on_next_render = false;
+        let available_height = usize::from(self.last_area.height);
+        let height_of = |index: usize| self.last_heights.get(index).copied().unwrap_or(1);
+        // Fill about half of the area above the selection while the selection stays fully visible
+        let max_above =
+            (available_height / 2).min(available_height.saturating_sub(height_of(index)));
+        let mut start = index;
+        let mut above = 0;
+        while start > 0 && above + height_of(start - 1) <= max_above {
+            start -= 1;
+            above += height_of(start);
+        }
+        let before = self.offset;
+        self.offset = start.min(self.last_biggest_index);
+        before != self.offset
+    }
+
     /// Scroll the specified amount of lines up
     ///
     /// Returns `true` when the scroll position changed.
     /// Returns `false` when the scrolling has reached the top.
-    pub fn scroll_up(&mut self, lines: usize) -> bool {
+    pub const fn scroll_up(&mut self, lines: usize) -> bool {
         let before = self.offset;
         self.offset = self.offset.saturating_sub(lines);
         before != self.offset
@@ -312,26 +1267,164 @@ where
         before != self.offset
     }
 
+    /// Scroll the text the specified amount of columns to the left.
+    /// `scroll_left(0)` resets to the beginning of the text.
+    ///
+    /// Returns `true` when the scroll position changed.
+    /// Returns `false` when the scrolling has reached the beginning of the text.
+    pub const fn scroll_left(&mut self, columns: usize) -> bool {
+        let before = self.x_offset;
+        self.x_offset = if columns == 0 {
+            0
+        } else {
+            self.x_offset.saturating_sub(columns)
+        };
+        before != self.x_offset
+    }
+
+    /// Scroll the text the specified amount of columns to the right
+    ///
+    /// Returns `true` when the scroll position changed.
+    /// Returns `false` when the scrolling has reached the end of the widest text of the last render.
+    pub fn scroll_right(&mut self, columns: usize) -> bool {
+        let before = self.x_offset;
+        self.x_offset = self
+            .x_offset
+            .saturating_add(columns)
+            .min(self.last_max_content_width.saturating_sub(1));
+        before != self.x_offset
+    }
+
+    /// Handles a crossterm key event with the common key bindings.
+    ///
+    /// | Key | Action |
+    /// | --- | --- |
+    /// | Up | [`key_up`](Self::key_up) |
+    /// | Down | [`key_down`](Self::key_down) |
+    /// | Left | [`key_left`](Self::key_left) |
+    /// | Right | [`key_right`](Self::key_right) |
+    /// | Space, Enter | [`toggle_selected`](Self::toggle_selected) |
+    /// | Home | [`select_first`](Self::select_first) |
+    /// | End | [`select_last`](Self::select_last) |
+    /// | Esc | Clear the selection |
+    ///
+    /// Key releases and other keys are ignored.
+    /// Use the methods above directly when different key bindings are needed.
+    ///
+    /// Returns `true` when the state changed.
+    #[cfg(feature = "crossterm")]
+    pub fn keyboard_event(&mut self, key: ratatui::crossterm::event::KeyEvent) -> bool {
+        use ratatui::crossterm::event::{KeyCode, KeyEventKind};
+        if key.kind == KeyEventKind::Release {
+            return false;
+        }
+        match key.code {
+            KeyCode::Up => self.key_up(),
+            KeyCode::Down => self.key_down(),
+            KeyCode::Left => self.key_left(),
+            KeyCode::Right => self.key_right(),
+            KeyCode::Char(' ') | KeyCode::Enter => self.toggle_selected(),
+            KeyCode::Home => self.select_first(),
+            KeyCode::End => self.select_last(),
+            KeyCode::Esc => self.select(Vec::new()),
+            _ => false,
+        }
+    }
+
+    /// Remember when the selection changed to provide the [`selection_age`](Self::selection_age).
+    ///
+    /// Disabled by default as reading the clock is not free and not supported on every platform like `wasm32-unknown-unknown`.
+    pub const fn set_track_selection_age(&mut self, track: bool) {
+        self.track_selection_age = track;
+        if !track {
+            self.last_selection_changed_at = None;
+        }
+    }
+
+    /// Remember when the selection changed when enabled with [`set_track_selection_age`](Self::set_track_selection_age).
+    fn selection_changed(&mut self) {
+        if self.track_selection_age {
+            self.last_selection_changed_at = Some(std::time::Instant::now());
+        }
+    }
+
+    /// Wrap around to the last node with [`key_up`](Self::key_up) on the first node
+    /// and to the first node with [`key_down`](Self::key_down) on the last node.
+    ///
+    /// Disabled by default.
+    pub const fn set_wrap_navigation(&mut self, wrap: bool) {
+        self.wrap_navigation = wrap;
+    }
+
     /// Handles the up arrow key.
     /// Moves up in the current depth or to its parent.
+    /// Wraps around to the last node when enabled with [`set_wrap_navigation`](Self::set_wrap_navigation).
     ///
     /// Returns `true` when the selection changed.
     pub fn key_up(&mut self) -> bool {
-        self.select_relative(|current| {
-            // When nothing is selected, fall back to end
-            current.map_or(usize::MAX, |current| current.saturating_sub(1))
-        })
+        let len = self.last_identifiers.len();
+        // When nothing is selected, fall back to end
+        let end = self.selected_index().unwrap_or(len);
+        let wrapped = if self.wrap_navigation { end..len } else { 0..0 };
+        let new_index = (0..end)
+            .rev()
+            .chain(wrapped.rev())
+            .find(|index| self.is_selectable_index(*index));
+        new_index.is_some_and(|new_index| self.select_relative(|_| new_index))
     }
 
     /// Handles the down arrow key.
     /// Moves down in the current depth or into a child node.
+    /// Wraps around to the first node when enabled with [`set_wrap_navigation`](Self::set_wrap_navigation).
     ///
     /// Returns `true` when the selection changed.
     pub fn key_down(&mut self) -> bool {
-        self.select_relative(|current| {
-            // When nothing is selected, fall back to start
-            current.map_or(0, |current| current.saturating_add(1))
-        })
+        let len = self.last_identifiers.len();
+        // When nothing is selected, fall back to start
+        let start = self.selected_index().map_or(0, |current| current + 1);
+        let wrapped = if self.wrap_navigation { 0..start } else { 0..0 };
+        let new_index = (start..len)
+            .chain(wrapped)
+            .find(|index| self.is_selectable_index(*index));
+        new_index.is_some_and(|new_index| self.select_relative(|_| new_index))
+    }
+
+    /// Moves down to the next node with children, skipping leaves.
+    /// Meant for the tab key but not bound by `keyboard_event` as tab commonly moves the focus between widgets.
+    /// Wraps around to the first node when enabled with [`set_wrap_navigation`](Self::set_wrap_navigation).
+    ///
+    /// Returns `true` when the selection changed.
+    pub fn key_tab(&mut self) -> bool {
+        let len = self.last_identifiers.len();
+        let start = self.selected_index().map_or(0, |current| current + 1);
+        let wrapped = if self.wrap_navigation { 0..start } else { 0..0 };
+        let new_index = (start..len).chain(wrapped).find(|index| {
+            self.is_selectable_index(*index)
+                && self
+                    .last_nodes
+                    .get(*index)
+                    .is_some_and(|node| node.has_children)
+        });
+        new_index.is_some_and(|new_index| self.select_relative(|_| new_index))
+    }
+
+    /// Moves up to the previous node with children, skipping leaves.
+    /// Counterpart of [`key_tab`](Self::key_tab) meant for shift + tab.
+    /// Wraps around to the last node when enabled with [`set_wrap_navigation`](Self::set_wrap_navigation).
+    ///
+    /// Returns `true` when the selection changed.
+    pub fn key_shift_tab(&mut self) -> bool {
+        let len = self.last_identifiers.len();
+        let end = self.selected_index().unwrap_or(len);
+        let wrapped = if self.wrap_navigation { end..len } else { 0..0 };
+        let new_index = (0..end).rev().chain(wrapped.rev()).find(|index| {
+            self.is_selectable_index(*index)
+                && self
+                    .last_nodes
+                    .get(*index)
+                    .is_some_and(|node| node.has_children)
+        });
+        new_index.is_some_and(|new_index| self.select_relative(|_| new_index))
     }
 
     /// Handles the left arrow key.
@@ -346,6 +1439,9 @@ where
             // Select the parent by removing the leaf from selection
             let popped = self.selected.pop();
             changed = popped.is_some();
+            if changed {
+                self.selection_changed();
+            }
         }
         changed
     }
@@ -364,3 +1460,824 @@ where
         }
     }
 }
+
+#[cfg(test)]
+fn render_example(state: &mut TreeState<&'static str>) -> ratatui::buffer::Buffer {
+    render_items(&TreeItem::example(), state)
+}
+
+#[cfg(test)]
+fn render_items(
+    items: &[TreeItem<'_, &'static str>],
+    state: &mut TreeState<&'static str>,
+) -> ratatui::buffer::Buffer {
+    use ratatui::buffer::Buffer;
+    use ratatui::widgets::StatefulWidget;
+
+    let tree = crate::Tree::new(items).unwrap();
+    let area = Rect::new(0, 0, 20, 10);
+    let mut buffer = Buffer::empty(area);
+    StatefulWidget::render(tree, area, &mut buffer, state);
+    buffer
+}
+
+#[test]
+fn scroll_center_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    state.open(vec!["b", "d"]);
+    render_example(&mut state);
+    assert!(!state.scroll_center());
+
+    state.select(vec!["h"]);
+    assert!(state.scroll_center());
+    assert_eq!(state.get_offset(), 2);
+    assert!(!state.ensure_selected_in_view_on_next_render);
+    assert!(!state.scroll_center());
+}
+
+#[test]
+fn scroll_center_respects_multiline_items() {
+    use ratatui::buffer::Buffer;
+    use ratatui::widgets::StatefulWidget;
+
+    let items = (0..20)
+        .map(|index| TreeItem::new_leaf(index, "one\ntwo\nthree"))
+        .collect::<Vec<_>>();
+    let render = |state: &mut TreeState<usize>| {
+        let tree = crate::Tree::new(&items).unwrap();
+        let area = Rect::new(0, 0, 10, 10);
+        StatefulWidget::render(tree, area, &mut Buffer::empty(area), state);
+    };
+    let mut state = TreeState::default();
+    render(&mut state);
+    state.select(vec![10]);
+    assert!(state.scroll_center());
+    assert_eq!(state.get_offset(), 9);
+    render(&mut state);
+    assert!(state
+        .rendered_at(Position::new(0, 3))
+        .is_some_and(|identifier| identifier == [10]));
+}
+
+#[test]
+fn select_first_and_last_visible_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    state.open(vec!["b", "d"]);
+    render_example(&mut state);
+    state.scroll_down(2);
+    render_example(&mut state);
+
+    assert!(state.select_first_visible());
+    assert_eq!(state.selected(), ["b", "c"]);
+    assert!(!state.select_first_visible());
+    assert!(state.select_last_visible());
+    assert_eq!(state.selected(), ["h"]);
+}
+
+#[test]
+fn ancestors_of_works() {
+    assert!(TreeState::<&str>::ancestors_of(&[]).is_empty());
+    assert!(TreeState::ancestors_of(&["a"]).is_empty());
+    assert_eq!(TreeState::ancestors_of(&["a", "b"]), [vec!["a"]]);
+}
+
+#[test]
+fn diff_works() {
+    let mut previous = TreeState::default();
+    previous.open(vec!["b"]);
+    previous.select(vec!["a"]);
+    let mut state = TreeState::default();
+    state.open(vec!["h"]);
+    state.select(vec!["a"]);
+
+    let diff = state.diff(&previous);
+    assert_eq!(diff.newly_opened, [vec!["h"]]);
+    assert_eq!(diff.newly_closed, [vec!["b"]]);
+    assert!(!diff.selection_changed);
+    assert_eq!(diff.previous_selection, Some(vec!["a"]));
+
+    state.select(Vec::new());
+    let diff = state.diff(&previous);
+    assert!(diff.selection_changed);
+    let diff = previous.diff(&state);
+    assert!(diff.selection_changed);
+    assert_eq!(diff.previous_selection, None);
+}
+
+#[test]
+fn page_selection_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    state.open(vec!["b", "d"]);
+    render_example(&mut state);
+    // Pretend a smaller area was rendered
+    state.last_area.height = 3;
+
+    assert!(state.page_down_selection());
+    assert_eq!(state.selected(), ["a"]);
+    assert!(state.page_down_selection());
+    assert_eq!(state.selected(), ["b", "d"]);
+    assert!(state.page_down_selection());
+    assert_eq!(state.selected(), ["b", "g"]);
+    assert!(state.page_up_selection());
+    assert_eq!(state.selected(), ["b", "d"]);
+    assert!(state.page_up_selection());
+    assert_eq!(state.selected(), ["a"]);
+}
+
+#[test]
+fn select_at_rendered_row_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    render_example(&mut state);
+    state.scroll_down(1);
+    render_example(&mut state);
+
+    assert!(state.select_at_rendered_row(1));
+    assert_eq!(state.selected(), ["b", "c"]);
+    assert!(!state.select_at_rendered_row(1));
+    assert!(!state.select_at_rendered_row(10));
+}
+
+#[test]
+fn extend_opened_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    let opened = state.extend_opened([vec!["b"], vec![], vec!["b", "d"], vec!["h"]]);
+    assert_eq!(opened, 2);
+    assert_eq!(state.opened_sorted(), [&["b"][..], &["b", "d"], &["h"]]);
+}
+
+#[test]
+fn reset_works() {
+    let mut state = TreeState::default();
+    state.set_wrap_navigation(true);
+    state.open(vec!["b"]);
+    state.select(vec!["b", "c"]);
+    render_example(&mut state);
+    state.scroll_down(1);
+
+    state.reset_view();
+    assert_eq!(state.get_offset(), 0);
+    assert_eq!(state.visible_node_count(), 0);
+    assert!(state.rendered_at(Position::new(0, 0)).is_none());
+    assert!(state.is_open(&["b"]));
+    assert_eq!(state.selected(), ["b", "c"]);
+
+    state.reset();
+    assert!(state.opened().is_empty());
+    assert!(state.selected().is_empty());
+    assert!(state.wrap_navigation);
+}
+
+#[test]
+fn is_reachable_works() {
+    let mut state = TreeState::default();
+    assert!(!state.is_reachable(&[]));
+    assert!(state.is_reachable(&["a"]));
+    assert!(!state.is_reachable(&["b", "d", "e"]));
+    state.open(vec!["b", "d"]);
+    assert!(!state.is_reachable(&["b", "d", "e"]));
+    state.open(vec!["b"]);
+    assert!(state.is_reachable(&["b", "d", "e"]));
+}
+
+#[test]
+fn select_child_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    render_example(&mut state);
+    assert!(!state.select_child());
+
+    state.select(vec!["a"]);
+    assert!(!state.select_child());
+    state.select(vec!["b"]);
+    assert!(state.select_child());
+    assert_eq!(state.selected(), ["b", "c"]);
+
+    state.select(vec!["b", "d"]);
+    assert!(!state.select_child());
+}
+
+#[test]
+fn mark_works() {
+    let mut state = TreeState::default();
+    assert!(!state.mark(Vec::new()));
+    assert!(state.mark(vec!["a"]));
+    assert!(!state.mark(vec!["a"]));
+    assert!(state.toggle_mark(vec!["b", "c"]));
+    assert_eq!(state.marked().len(), 2);
+    assert!(state.toggle_mark(vec!["b", "c"]));
+    assert!(state.unmark(&["a"]));
+    assert!(!state.unmark(&["a"]));
+    assert!(state.marked().is_empty());
+}
+
+#[test]
+fn total_required_height_works() {
+    let mut state = TreeState::default();
+    assert_eq!(state.total_required_height(), 0);
+    render_example(&mut state);
+    assert_eq!(state.total_required_height(), 3);
+    state.open(vec!["b"]);
+    render_example(&mut state);
+    assert_eq!(state.total_required_height(), 6);
+}
+
+#[test]
+fn visible_range_works() {
+    let mut state = TreeState::default();
+    assert_eq!(state.visible_range(), 0..0);
+    state.open(vec!["b"]);
+    render_example(&mut state);
+    assert_eq!(state.visible_range(), 0..6);
+    state.scroll_down(2);
+    render_example(&mut state);
+    assert_eq!(state.visible_range(), 2..6);
+}
+
+#[test]
+fn selection_path_display_works() {
+    let names = ["Alfa", "Bravo", "Charlie", "Delta", "Echo"];
+    let mut state = TreeState::<usize>::default();
+    assert_eq!(
+        state.selection_path_display(|identifier| names[*identifier]),
+        ""
+    );
+    state.select(vec![1, 4]);
+    assert_eq!(
+        state.selection_path_display(|identifier| names[*identifier]),
+        "Bravo / Echo"
+    );
+}
+
+#[test]
+fn find_visible_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    render_example(&mut state);
+    assert_eq!(
+        state.find_visible(|identifier| identifier.ends_with(&["d"])),
+        Some(&["b", "d"][..])
+    );
+    assert_eq!(state.find_visible(|identifier| identifier.len() > 2), None);
+
+    assert!(state.find_and_select(|identifier| identifier.contains(&"g")));
+    assert_eq!(state.selected(), ["b", "g"]);
+    assert!(!state.find_and_select(|identifier| identifier.contains(&"e")));
+}
+
+#[test]
+fn select_next_where_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    render_example(&mut state);
+    let is_leaf = |identifier: &[&str]| ["a", "c", "g", "h"].contains(identifier.last().unwrap());
+
+    assert!(state.select_next_where(is_leaf));
+    assert_eq!(state.selected(), ["a"]);
+    assert!(state.select_next_where(is_leaf));
+    assert_eq!(state.selected(), ["b", "c"]);
+    assert!(state.select_next_where(is_leaf));
+    assert!(state.select_next_where(is_leaf));
+    assert_eq!(state.selected(), ["h"]);
+    assert!(state.select_next_where(is_leaf));
+    assert_eq!(state.selected(), ["a"]);
+    assert!(!state.select_next_where(|identifier| identifier == ["a"]));
+}
+
+#[test]
+fn clone_renders_the_same() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    state.select(vec!["b", "d"]);
+    let buffer = render_example(&mut state);
+    let mut cloned = state.clone();
+    assert_eq!(render_example(&mut cloned), buffer);
+    assert_eq!(cloned.selected(), state.selected());
+}
+
+#[test]
+fn last_rendered_area_works() {
+    let mut state = TreeState::default();
+    assert_eq!(state.last_rendered_area(), Rect::default());
+    render_example(&mut state);
+    assert_eq!(state.last_rendered_area(), Rect::new(0, 0, 20, 10));
+}
+
+#[test]
+fn count_opened_in_subtree_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    state.open(vec!["b", "d"]);
+    state.open(vec!["h"]);
+    assert_eq!(state.count_opened_in_subtree(&["b"]), 2);
+    assert_eq!(state.count_opened_in_subtree(&["b", "d"]), 1);
+    assert_eq!(state.count_opened_in_subtree(&["a"]), 0);
+}
+
+#[test]
+fn select_root_works() {
+    let mut state = TreeState::default();
+    assert!(!state.select_root());
+    state.select(vec!["b", "d", "e"]);
+    assert!(state.select_root());
+    assert_eq!(state.selected(), ["b"]);
+    assert!(!state.select_root());
+}
+
+#[test]
+fn nth_visible_and_visible_position_of_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    render_example(&mut state);
+    assert_eq!(state.nth_visible(2), Some(&["b", "c"][..]));
+    assert_eq!(state.nth_visible(6), None);
+    assert_eq!(state.visible_position_of(&["b", "g"]), Some(4));
+    assert_eq!(state.visible_position_of(&["b", "d", "e"]), None);
+}
+
+#[test]
+fn click_at_relative_works() {
+    let mut state = TreeState::default();
+    let items = TreeItem::example();
+    let tree = crate::Tree::new(&items)
+        .unwrap()
+        .block(ratatui::widgets::Block::bordered());
+    let area = Rect::new(5, 3, 20, 10);
+    let mut buffer = ratatui::buffer::Buffer::empty(area);
+    ratatui::widgets::StatefulWidget::render(tree, area, &mut buffer, &mut state);
+
+    assert!(!state.click_at(Position::new(5, 3)));
+    assert!(state.click_at(Position::new(6, 5)));
+    assert_eq!(state.selected(), ["b"]);
+    assert!(state.click_at_relative(Position::new(0, 0)));
+    assert_eq!(state.selected(), ["a"]);
+    assert!(!state.click_at_relative(Position::new(0, 8)));
+}
+
+#[test]
+fn siblings_of_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    state.open(vec!["b", "d"]);
+    render_example(&mut state);
+
+    assert!(state.current_siblings().is_empty());
+    assert_eq!(state.siblings_of(&["h"]), [&["a"][..], &["b"], &["h"]]);
+    state.select(vec!["b", "d"]);
+    assert_eq!(
+        state.current_siblings(),
+        [&["b", "c"][..], &["b", "d"], &["b", "g"]]
+    );
+    assert_eq!(
+        state.siblings_of(&["b", "d", "e"]),
+        [&["b", "d", "e"][..], &["b", "d", "f"]]
+    );
+}
+
+#[test]
+fn selection_age_works() {
+    let mut state = TreeState::default();
+    assert!(state.select(vec!["a"]));
+    assert!(state.selection_age().is_none());
+    assert!(state.select(Vec::new()));
+
+    state.set_track_selection_age(true);
+    assert!(state.selection_age().is_none());
+    assert!(!state.select(Vec::new()));
+    assert!(state.selection_age().is_none());
+
+    state.select(vec!["b", "c"]);
+    let first = state.last_selection_changed_at.unwrap();
+    assert!(state.selection_age().is_some());
+    state.select(vec!["b", "c"]);
+    assert_eq!(state.last_selection_changed_at, Some(first));
+
+    state.key_left();
+    assert!(state.last_selection_changed_at.unwrap() >= first);
+
+    state.set_track_selection_age(false);
+    assert!(state.selection_age().is_none());
+    state.select(vec!["a"]);
+    assert!(state.selection_age().is_none());
+}
+
+#[test]
+fn key_tab_skips_leaves() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    render_example(&mut state);
+
+    assert!(state.key_tab());
+    assert_eq!(state.selected(), ["b"]);
+    assert!(state.key_tab());
+    assert_eq!(state.selected(), ["b", "d"]);
+    assert!(!state.key_tab());
+    assert!(state.key_shift_tab());
+    assert_eq!(state.selected(), ["b"]);
+    assert!(!state.key_shift_tab());
+
+    state.set_wrap_navigation(true);
+    assert!(state.key_shift_tab());
+    assert_eq!(state.selected(), ["b", "d"]);
+    assert!(state.key_tab());
+    assert_eq!(state.selected(), ["b"]);
+}
+
+#[test]
+fn node_for_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    render_example(&mut state);
+
+    let node = state.node_for(&["b"]).unwrap();
+    assert_eq!(node.depth, 0);
+    assert!(node.has_children);
+    assert!(!node.is_separator);
+    assert_eq!(node.height, 1);
+
+    let node = state.node_for(&["b", "c"]).unwrap();
+    assert_eq!(node.depth, 1);
+    assert!(!node.has_children);
+
+    assert!(state.node_for(&["b", "d", "e"]).is_none());
+}
+
+#[test]
+fn visible_node_count_works() {
+    let mut state = TreeState::default();
+    assert_eq!(state.visible_node_count(), 0);
+    render_example(&mut state);
+    assert_eq!(state.visible_node_count(), 3);
+    state.open(vec!["b"]);
+    render_example(&mut state);
+    assert_eq!(state.visible_node_count(), 6);
+}
+
+#[test]
+fn wrap_navigation_works() {
+    let mut state = TreeState::default();
+    render_example(&mut state);
+    state.select(vec!["a"]);
+    assert!(!state.key_up());
+    state.select(vec!["h"]);
+    assert!(!state.key_down());
+
+    state.set_wrap_navigation(true);
+    assert!(state.key_down());
+    assert_eq!(state.selected(), ["a"]);
+    assert!(state.key_up());
+    assert_eq!(state.selected(), ["h"]);
+}
+
+#[test]
+fn select_child_skips_separators() {
+    let items = [TreeItem::new(
+        "p",
+        "Parent",
+        vec![
+            TreeItem::new_separator("s"),
+            TreeItem::new_leaf("c", "Child"),
+        ],
+    )
+    .unwrap()];
+    let mut state = TreeState::default();
+    state.open(vec!["p"]);
+    render_items(&items, &mut state);
+
+    state.select(vec!["p"]);
+    assert!(state.select_child());
+    assert_eq!(state.selected(), ["p", "c"]);
+}
+
+#[test]
+fn select_sibling_skips_separators() {
+    let items = [
+        TreeItem::new_leaf("a", "Alfa"),
+        TreeItem::new_separator("s"),
+        TreeItem::new_leaf("b", "Bravo"),
+    ];
+    let mut state = TreeState::default();
+    render_items(&items, &mut state);
+
+    state.select(vec!["a"]);
+    assert!(state.select_next_sibling());
+    assert_eq!(state.selected(), ["b"]);
+    assert!(state.select_prev_sibling());
+    assert_eq!(state.selected(), ["a"]);
+}
+
+#[test]
+fn select_sibling_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    state.open(vec!["b", "d"]);
+    render_example(&mut state);
+
+    assert!(!state.select_next_sibling());
+    state.select(vec!["b", "c"]);
+    assert!(!state.select_prev_sibling());
+    assert!(state.select_next_sibling());
+    assert_eq!(state.selected(), ["b", "d"]);
+    assert!(state.select_next_sibling());
+    assert_eq!(state.selected(), ["b", "g"]);
+    assert!(!state.select_next_sibling());
+    assert!(state.select_prev_sibling());
+    assert_eq!(state.selected(), ["b", "d"]);
+
+    state.select(vec!["a"]);
+    assert!(state.select_next_sibling());
+    assert_eq!(state.selected(), ["b"]);
+    assert!(state.select_next_sibling());
+    assert_eq!(state.selected(), ["h"]);
+}
+
+#[test]
+fn scroll_horizontal_works() {
+    let mut state = TreeState::default();
+    render_example(&mut state);
+    assert!(!state.scroll_left(1));
+    assert!(state.scroll_right(2));
+    assert_eq!(state.get_x_offset(), 2);
+    // "Bravo" and "Hotel" are the widest
+    assert!(state.scroll_right(10));
+    assert_eq!(state.get_x_offset(), 4);
+    assert!(!state.scroll_right(1));
+    assert!(state.scroll_left(usize::MAX));
+    assert_eq!(state.get_x_offset(), 0);
+
+    state.scroll_right(3);
+    assert!(state.scroll_left(0));
+    assert_eq!(state.get_x_offset(), 0);
+    assert!(!state.scroll_left(0));
+}
+
+#[cfg(feature = "crossterm")]
+#[test]
+fn keyboard_event_works() {
+    use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+    let mut state = TreeState::default();
+    render_example(&mut state);
+    assert!(state.keyboard_event(KeyEvent::from(KeyCode::Down)));
+    assert!(state.keyboard_event(KeyEvent::from(KeyCode::Down)));
+    assert_eq!(state.selected(), ["b"]);
+    assert!(state.keyboard_event(KeyEvent::from(KeyCode::Enter)));
+    assert!(state.is_open(&["b"]));
+    assert!(!state.keyboard_event(KeyEvent::from(KeyCode::Char('x'))));
+    assert!(state.keyboard_event(KeyEvent::from(KeyCode::Esc)));
+    assert!(state.selected().is_empty());
+}
+
+#[test]
+fn is_open_and_is_selected_work() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    state.select(vec!["b", "c"]);
+    assert!(state.is_open(&["b"]));
+    assert!(!state.is_open(&["b", "c"]));
+    assert!(state.is_selected(&["b", "c"]));
+    assert!(!state.is_selected(&["b"]));
+}
+
+#[test]
+fn opened_sorted_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b", "d"]);
+    state.open(vec!["i"]);
+    state.open(vec!["b"]);
+    let expected: [&[&str]; 3] = [&["b"], &["b", "d"], &["i"]];
+    assert_eq!(state.opened_sorted(), expected);
+}
+
+#[test]
+fn opened_by_depth_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b", "d"]);
+    state.open(vec!["i"]);
+    state.open(vec!["b"]);
+    let expected: [&[&str]; 3] = [&["b"], &["i"], &["b", "d"]];
+    assert_eq!(state.opened_by_depth(), expected);
+}
+
+#[test]
+fn open_all_below_works() {
+    let items = TreeItem::example();
+    let mut state = TreeState::default();
+    assert_eq!(state.open_all_below(&items, &["a"]), 0);
+    assert_eq!(state.open_all_below(&items, &["b", "d"]), 1);
+    assert_eq!(state.open_all_below(&items, &["b"]), 1);
+    let expected: [&[&str]; 2] = [&["b"], &["b", "d"]];
+    assert_eq!(state.opened_sorted(), expected);
+
+    let mut state = TreeState::default();
+    assert_eq!(state.open_all_below(&items, &[]), 2);
+    assert_eq!(state.opened_sorted(), expected);
+}
+
+#[test]
+fn close_all_below_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    state.open(vec!["b", "d"]);
+    state.open(vec!["b", "d", "e"]);
+    state.open(vec!["i"]);
+    assert_eq!(state.close_all_below(&["b", "d"]), 2);
+    assert_eq!(state.close_all_below(&["b"]), 1);
+    assert_eq!(state.close_all_below(&["b"]), 0);
+    assert_eq!(state.opened_sorted(), [["i"]]);
+}
+
+#[test]
+fn close_others_works() {
+    let mut state = TreeState::default();
+    state.open(vec!["b"]);
+    state.open(vec!["b", "d"]);
+    state.open(vec!["i"]);
+    state.select(vec!["b", "d", "e"]);
+    assert_eq!(state.close_others(), 1);
+    let expected: [&[&str]; 2] = [&["b"], &["b", "d"]];
+    assert_eq!(state.opened_sorted(), expected);
+    assert_eq!(state.close_others(), 0);
+}
+
+#[test]
+fn select_parent_works() {
+    let mut state = TreeState::default();
+    assert!(!state.select_parent());
+    state.select(vec!["b", "d", "e"]);
+    assert!(state.select_parent());
+    assert_eq!(state.selected(), ["b", "d"]);
+    assert!(state.select_parent());
+    assert_eq!(state.selected(), ["b"]);
+    assert!(!state.select_parent());
+    assert_eq!(state.selected(), ["b"]);
+}
+
+#[test]
+fn move_to_identifier_works() {
+    let mut state = TreeState::default();
+    assert!(state.move_to_identifier(vec!["b", "d", "e"]));
+    let expected: [&[&str]; 2] = [&["b"], &["b", "d"]];
+    assert_eq!(state.opened_sorted(), expected);
+    assert_eq!(state.selected(), ["b", "d", "e"]);
+    assert!(!state.move_to_identifier(vec!["b", "d", "e"]));
+}
+
+#[test]
+fn open_to_depth_works() {
+    let items = TreeItem::example();
+    let mut state = TreeState::default();
+    assert_eq!(state.open_to_depth(&items, 1), 1);
+    assert_eq!(state.opened_sorted(), [["b"]]);
+    assert_eq!(state.open_to_depth(&items, 2), 1);
+    assert_eq!(state.open_to_depth(&items, usize::MAX), 0);
+    let expected: [&[&str]; 2] = [&["b"], &["b", "d"]];
+    assert_eq!(state.opened_sorted(), expected);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_roundtrip() {
+    let mut state = TreeState::default();
+    state.open(vec!["b".to_owned()]);
+    state.open(vec!["b".to_owned(), "d".to_owned()]);
+    state.select(vec!["b".to_owned(), "c".to_owned()]);
+    state.mark(vec!["h".to_owned()]);
+    let json = serde_json::to_string(&state).unwrap();
+    let restored = serde_json::from_str::<TreeState<String>>(&json).unwrap();
+    assert_eq!(restored.opened(), state.opened());
+    assert_eq!(restored.selected(), state.selected());
+    assert_eq!(restored.marked(), state.marked());
+}
+
+#[cfg(test)]
+mod proptests {
+    use proptest::prelude::*;
+    use ratatui::buffer::Buffer;
+    use ratatui::widgets::StatefulWidget;
+
+    use super::*;
+    use crate::Tree;
+
+    const PATHS: [&[&str]; 8] = [
+        &["a"],
+        &["b"],
+        &["b", "c"],
+        &["b", "d"],
+        &["b", "d", "e"],
+        &["b", "d", "f"],
+        &["b", "g"],
+        &["h"],
+    ];
+
+    #[derive(Debug, Clone)]
+    enum Operation {
+        Open(usize),
+        Close(usize),
+        Select(usize),
+        KeyUp,
+        KeyDown,
+        KeyLeft,
+        KeyRight,
+        ToggleSelected,
+        ScrollUp(usize),
+        ScrollDown(usize),
+        Render(u16),
+    }
+
+    fn operation() -> impl Strategy<Value = Operation> {
+        prop_oneof![
+            (0..PATHS.len()).prop_map(Operation::Open),
+            (0..PATHS.len()).prop_map(Operation::Close),
+            (0..PATHS.len()).prop_map(Operation::Select),
+            Just(Operation::KeyUp),
+            Just(Operation::KeyDown),
+            Just(Operation::KeyLeft),
+            Just(Operation::KeyRight),
+            Just(Operation::ToggleSelected),
+            (0..10_usize).prop_map(Operation::ScrollUp),
+            (0..10_usize).prop_map(Operation::ScrollDown),
+            (1..10_u16).prop_map(Operation::Render),
+        ]
+    }
+
+    fn apply(state: &mut TreeState<&'static str>, operation: &Operation) {
+        match *operation {
+            Operation::Open(index) => _ = state.open(PATHS[index].to_vec()),
+            Operation::Close(index) => _ = state.close(PATHS[index]),
+            Operation::Select(index) => _ = state.select(PATHS[index].to_vec()),
+            Operation::KeyUp => _ = state.key_up(),
+            Operation::KeyDown => _ = state.key_down(),
+            Operation::KeyLeft => _ = state.key_left(),
+            Operation::KeyRight => _ = state.key_right(),
+            Operation::ToggleSelected => _ = state.toggle_selected(),
+            Operation::ScrollUp(lines) => _ = state.scroll_up(lines),
+            Operation::ScrollDown(lines) => _ = state.scroll_down(lines),
+            Operation::Render(_) => {}
+        }
+    }
+
+    fn render(state: &mut TreeState<&'static str>, height: u16) {
+        let items = TreeItem::example();
+        let tree = Tree::new(&items).unwrap();
+        let area = Rect::new(0, 0, 20, height);
+        let mut buffer = Buffer::empty(area);
+        StatefulWidget::render(tree, area, &mut buffer, state);
+    }
+
+    fn ancestors_open(state: &TreeState<&'static str>) -> bool {
+        (1..state.selected.len()).all(|length| state.opened.contains(&state.selected[..length]))
+    }
+
+    proptest! {
+        #[test]
+        fn navigation_invariants(operations in prop::collection::vec(operation(), 0..50)) {
+            let mut state = TreeState::default();
+            let mut height = 5;
+            render(&mut state, height);
+            for operation in &operations {
+                apply(&mut state, operation);
+                if let Operation::Render(new_height) = *operation {
+                    height = new_height;
+                }
+                let ensure_in_view = state.ensure_selected_in_view_on_next_render;
+                render(&mut state, height);
+
+                prop_assert!(state.offset <= state.last_biggest_index);
+                prop_assert_eq!(state.last_biggest_index, state.last_identifiers.len() - 1);
+                if state.selected.is_empty() {
+                    continue;
+                }
+                let is_existing = PATHS.iter().any(|path| *path == state.selected);
+                if is_existing && ancestors_open(&state) {
+                    let position = state.last_identifiers.iter().position(|identifier| *identifier == state.selected);
+                    prop_assert!(position.is_some_and(|position| position <= state.last_biggest_index));
+                    if ensure_in_view {
+                        let is_rendered = state.last_rendered_identifiers.iter().any(|(_, identifier)| *identifier == state.selected);
+                        prop_assert!(is_rendered);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn key_down_and_up_return(operations in prop::collection::vec(operation(), 0..20)) {
+            let mut state = TreeState::default();
+            render(&mut state, 5);
+            for operation in &operations {
+                apply(&mut state, operation);
+                render(&mut state, 5);
+            }
+            let before = state.selected.clone();
+            if state.key_down() && !before.is_empty() && state.last_identifiers.contains(&before) {
+                render(&mut state, 5);
+                prop_assert!(state.key_up());
+                prop_assert_eq!(state.selected, before);
+            }
+        }
+    }
+}